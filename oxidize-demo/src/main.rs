@@ -1,5 +1,16 @@
 extern crate oxidize;
 
+use oxidize::prelude::*;
+
 fn main() {
-    println!("Demoooo");
+    // sin^2(x) sampled over [0, 1]
+    let data = [
+        (0.0,  0.0),
+        (0.25, 0.0612087),
+        (0.5,  0.229849),
+        (0.75, 0.464631),
+        (1.0,  0.708073)];
+
+    println!("trapezoidal: {}", trapezoidal(&data));
+    println!("simpson:     {}", simpson(&data));
 }
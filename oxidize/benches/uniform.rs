@@ -0,0 +1,76 @@
+//! Compares the vectorised `_dx` rules with the scalar rules they speed up.
+//!
+//! `try_dx` checks every ordinate before adding them up, while `simd_dx`
+//! checks them only when the result is not finite, so on finite data part
+//! of the gap is the pass `try_dx` spends checking. `dx` does no checking
+//! at all and shows the gap due to adding up alone; `pairs` reads twice
+//! the memory, for reference.
+//!
+//! Run with `cargo bench -p oxidize`.
+
+#[macro_use]
+extern crate criterion;
+extern crate oxidize;
+
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, Throughput};
+use oxidize::integrate::{simd_simpson_dx, simd_trapezoidal_dx, simpson, simpson_dx, trapezoidal, trapezoidal_dx};
+use oxidize::integrate::{try_simpson_dx, try_trapezoidal_dx};
+
+const SIZES: [usize; 3] = [1_001, 100_001, 10_000_001];
+
+fn samples(n: usize) -> (f64, Vec<f64>) {
+    let dx = 1.0/(n - 1) as f64;
+    let ys = (0..n).map(|i| (i as f64*dx).sin()).collect();
+    (dx, ys)
+}
+
+fn bench_trapezoidal(c: &mut Criterion) {
+    let mut group = c.benchmark_group("trapezoidal");
+    for &n in &SIZES {
+        let (dx, ys) = samples(n);
+        let pairs: Vec<(f64, f64)> = ys.iter().enumerate().map(|(i, &y)| (i as f64*dx, y)).collect();
+        group.throughput(Throughput::Elements(n as u64));
+
+        group.bench_with_input(BenchmarkId::new("pairs", n), &pairs, |b, pairs| {
+            b.iter(|| trapezoidal(black_box(pairs)))
+        });
+        group.bench_with_input(BenchmarkId::new("dx", n), &ys, |b, ys| {
+            b.iter(|| trapezoidal_dx(dx, black_box(ys)))
+        });
+        group.bench_with_input(BenchmarkId::new("try_dx", n), &ys, |b, ys| {
+            b.iter(|| try_trapezoidal_dx(dx, black_box(ys)))
+        });
+        group.bench_with_input(BenchmarkId::new("simd_dx", n), &ys, |b, ys| {
+            b.iter(|| simd_trapezoidal_dx(dx, black_box(ys)))
+        });
+    }
+    group.finish();
+}
+
+fn bench_simpson(c: &mut Criterion) {
+    let mut group = c.benchmark_group("simpson");
+    for &n in &SIZES {
+        let (dx, ys) = samples(n);
+        let pairs: Vec<(f64, f64)> = ys.iter().enumerate().map(|(i, &y)| (i as f64*dx, y)).collect();
+        group.throughput(Throughput::Elements(n as u64));
+
+        group.bench_with_input(BenchmarkId::new("pairs", n), &pairs, |b, pairs| {
+            b.iter(|| simpson(black_box(pairs)))
+        });
+        group.bench_with_input(BenchmarkId::new("dx", n), &ys, |b, ys| {
+            b.iter(|| simpson_dx(dx, black_box(ys)))
+        });
+        group.bench_with_input(BenchmarkId::new("try_dx", n), &ys, |b, ys| {
+            b.iter(|| try_simpson_dx(dx, black_box(ys)))
+        });
+        group.bench_with_input(BenchmarkId::new("simd_dx", n), &ys, |b, ys| {
+            b.iter(|| simd_simpson_dx(dx, black_box(ys)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_trapezoidal, bench_simpson);
+criterion_main!(benches);
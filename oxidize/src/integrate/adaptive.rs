@@ -0,0 +1,332 @@
+//! Adaptive Simpson quadrature.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[cfg(feature = "parallel")]
+use rayon;
+
+use sum::Summation;
+
+use super::function::{check_interval, Counted};
+use super::{kernel, IntegrationError, Ordinate, QuadratureResult, Termination, Tolerance};
+
+/// The number of times an interval may be halved before its estimate is
+/// accepted regardless of the tolerance.
+pub const MAX_ADAPTIVE_DEPTH: usize = 50;
+
+/// The most evaluations of the integrand `adaptive_simpson` spends before
+/// accepting the remaining intervals as they are.
+pub const MAX_ADAPTIVE_EVALUATIONS: usize = 1_000_000;
+
+/// The number of levels below the whole interval that `par_adaptive_simpson`
+/// refines on separate threads. Deeper intervals are refined serially, so
+/// at most `2^PARALLEL_LEVELS` tasks are spawned however deep the
+/// recursion goes.
+#[cfg(feature = "parallel")]
+const PARALLEL_LEVELS: usize = 8;
+
+/// Computes the integral of `f` over `[a, b]` using Simpson's rule,
+/// halving each interval until the tolerance is met.
+///
+/// Each interval is compared against the sum of its two halves; when they
+/// agree to within 15 times the interval's share of the tolerance, the
+/// halves are accepted with a Richardson correction. The error estimate is
+/// the sum of the differences over all accepted intervals. The tolerance
+/// is measured against a first five-point estimate of the integral, and is
+/// never tighter than rounding allows for the integral of `|f|`, so an
+/// integral of zero with a relative tolerance still converges.
+///
+/// `f` may return any `Ordinate`, such as a `Complex<f64>`; differences
+/// and tolerances are then measured with its `norm`.
+///
+/// An interval halved `MAX_ADAPTIVE_DEPTH` times, or too narrow to halve,
+/// is accepted as it is and reported in the result's `termination`, as are
+/// all the intervals left once `MAX_ADAPTIVE_EVALUATIONS` is reached.
+///
+/// Fails if either bound is not finite or if the tolerance is invalid.
+///
+/// ```
+/// use oxidize::integrate::{adaptive_simpson, Tolerance};
+///
+/// let result = adaptive_simpson(f64::exp, 0.0, 1.0, Tolerance::absolute(1e-10)).unwrap();
+/// assert!((result.value - (1f64.exp() - 1.0)).abs() < 1e-10);
+/// ```
+pub fn adaptive_simpson<Y, F>(f: F, a: f64, b: f64, tolerance: Tolerance) -> Result<QuadratureResult<Y>, IntegrationError>
+    where Y: Ordinate<f64>, F: Fn(f64) -> Y
+{
+    solve(&f, a, b, tolerance, |f, whole, halves, target, budget| {
+        refine(f, whole, halves, target, MAX_ADAPTIVE_DEPTH, budget)
+    })
+}
+
+/// Computes an integral as `adaptive_simpson` does, refining the two
+/// halves of every interval on separate threads, down to a fixed depth.
+///
+/// The result is the same as `adaptive_simpson`'s whenever fewer than
+/// `MAX_ADAPTIVE_EVALUATIONS` evaluations are needed. Past that, which
+/// intervals are left unrefined depends on the order the threads reach
+/// them in.
+///
+/// Fails as `adaptive_simpson` does.
+///
+/// ```
+/// use oxidize::integrate::{adaptive_simpson, par_adaptive_simpson, Tolerance};
+///
+/// let f = |x: f64| 1.0/(1e-4 + (x - 0.3)*(x - 0.3));
+/// let serial = adaptive_simpson(f, 0.0, 1.0, Tolerance::relative(1e-10)).unwrap();
+/// let parallel = par_adaptive_simpson(f, 0.0, 1.0, Tolerance::relative(1e-10)).unwrap();
+/// assert_eq!(serial, parallel);
+/// ```
+#[cfg(feature = "parallel")]
+pub fn par_adaptive_simpson<Y, F>(f: F, a: f64, b: f64, tolerance: Tolerance) -> Result<QuadratureResult<Y>, IntegrationError>
+    where Y: Ordinate<f64> + Send, F: Fn(f64) -> Y + Sync
+{
+    solve(&f, a, b, tolerance, |f, whole, halves, target, budget| {
+        par_refine(f, whole, halves, target, MAX_ADAPTIVE_DEPTH, budget)
+    })
+}
+
+/// Checks the arguments, makes the first estimate and the target, and
+/// hands them to `run` to refine.
+fn solve<Y, F, R>(f: &F, a: f64, b: f64, tolerance: Tolerance, run: R) -> Result<QuadratureResult<Y>, IntegrationError>
+    where Y: Ordinate<f64>,
+          F: Fn(f64) -> Y,
+          R: FnOnce(&Counted<F>, Panel<Y>, (Panel<Y>, Panel<Y>), f64, &AtomicUsize) -> Piece<Y>
+{
+    check_interval(a, b)?;
+    tolerance.check()?;
+
+    let f = Counted::new(f);
+    let m = 0.5*(a + b);
+    let whole = Panel::new(a, b, f.call(a), f.call(m), f.call(b));
+    let halves = whole.split(&f);
+
+    // Rounding alone limits the accuracy to a few ulps of the integral of
+    // |f|, whatever the integral itself comes to.
+    let magnitude = halves.0.magnitude() + halves.1.magnitude();
+    let target = tolerance.target((halves.0.estimate + halves.1.estimate).norm())
+        .max(50.0*f64::EPSILON*magnitude);
+
+    let budget = AtomicUsize::new(MAX_ADAPTIVE_EVALUATIONS.saturating_sub(f.evaluations()));
+    let piece = run(&f, whole, halves, target, &budget);
+
+    Ok(QuadratureResult {
+        value: piece.value,
+        error: piece.error,
+        evaluations: f.evaluations(),
+        termination: piece.termination,
+    })
+}
+
+/// An interval together with the integrand at its ends and midpoint.
+#[derive(Clone, Copy)]
+struct Panel<Y> {
+    a: f64,
+    b: f64,
+    fa: Y,
+    fm: Y,
+    fb: Y,
+    estimate: Y,
+}
+
+impl<Y: Ordinate<f64>> Panel<Y> {
+    fn new(a: f64, b: f64, fa: Y, fm: Y, fb: Y) -> Panel<Y> {
+        let y = [fa, fm, fb];
+        let estimate = kernel::simpson(Summation::Naive, 3, 0.5*(b - a), |i| y[i]);
+
+        Panel { a, b, fa, fm, fb, estimate }
+    }
+
+    /// Simpson's estimate of the integral of `|f|` over the panel.
+    fn magnitude(&self) -> f64 {
+        Panel::new(self.a, self.b, self.fa.norm(), self.fm.norm(), self.fb.norm()).estimate
+    }
+
+    fn split<F: Fn(f64) -> Y>(&self, f: &Counted<F>) -> (Panel<Y>, Panel<Y>) {
+        let m = 0.5*(self.a + self.b);
+        let left = Panel::new(self.a, m, self.fa, f.call(0.5*(self.a + m)), self.fm);
+        let right = Panel::new(m, self.b, self.fm, f.call(0.5*(m + self.b)), self.fb);
+
+        (left, right)
+    }
+}
+
+/// The integral over one or more accepted intervals, with its error and
+/// termination.
+struct Piece<Y> {
+    value: Y,
+    error: f64,
+    termination: Termination,
+}
+
+impl<Y: Ordinate<f64>> Piece<Y> {
+    /// Joins the pieces of two neighbouring intervals. A termination other
+    /// than `Converged` on the right wins, as the last one met would.
+    fn join(self, right: Piece<Y>) -> Piece<Y> {
+        let termination = if right.termination == Termination::Converged {
+            self.termination
+        } else {
+            right.termination
+        };
+
+        Piece { value: self.value + right.value, error: self.error + right.error, termination }
+    }
+}
+
+/// Accepts `whole`, given its two `halves`, if they agree to within
+/// `target` or it cannot be halved again. Otherwise takes the evaluations
+/// halving both halves costs from `budget` and returns `None`.
+fn accept<Y: Ordinate<f64>>(whole: &Panel<Y>, halves: &(Panel<Y>, Panel<Y>), target: f64, depth: usize, budget: &AtomicUsize) -> Option<Piece<Y>> {
+    let (ref left, ref right) = *halves;
+    let delta = left.estimate + right.estimate - whole.estimate;
+
+    // Stop once the midpoints can no longer be told apart from the ends,
+    // otherwise the recursion would keep evaluating the same points.
+    let m = left.b;
+    let too_small = !(whole.a < m && m < whole.b);
+
+    let converged = delta.norm() <= 15.0*target;
+    if !(depth == 0 || too_small || converged) && take(budget, 4) {
+        return None;
+    }
+
+    let termination = if converged {
+        Termination::Converged
+    } else if too_small {
+        Termination::IntervalTooSmall
+    } else {
+        Termination::SubdivisionLimit
+    };
+
+    Some(Piece {
+        value: left.estimate + right.estimate + delta/15.0,
+        error: delta.norm()/15.0,
+        termination,
+    })
+}
+
+/// Takes `n` evaluations from `budget`, unless fewer are left.
+fn take(budget: &AtomicUsize, n: usize) -> bool {
+    budget.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |left| left.checked_sub(n)).is_ok()
+}
+
+/// Integrates `whole`, given its two `halves`, to within `target`.
+fn refine<Y, F>(f: &Counted<F>, whole: Panel<Y>, halves: (Panel<Y>, Panel<Y>), target: f64, depth: usize, budget: &AtomicUsize) -> Piece<Y>
+    where Y: Ordinate<f64>, F: Fn(f64) -> Y
+{
+    if let Some(piece) = accept(&whole, &halves, target, depth, budget) {
+        return piece;
+    }
+
+    let (left, right) = halves;
+    let left_halves = left.split(f);
+    let right_halves = right.split(f);
+    refine(f, left, left_halves, 0.5*target, depth - 1, budget)
+        .join(refine(f, right, right_halves, 0.5*target, depth - 1, budget))
+}
+
+/// Integrates `whole` as `refine` does, refining the two halves on
+/// separate threads down to `PARALLEL_LEVELS` levels below the whole
+/// interval and serially below that.
+#[cfg(feature = "parallel")]
+fn par_refine<Y, F>(f: &Counted<F>, whole: Panel<Y>, halves: (Panel<Y>, Panel<Y>), target: f64, depth: usize, budget: &AtomicUsize) -> Piece<Y>
+    where Y: Ordinate<f64> + Send, F: Fn(f64) -> Y + Sync
+{
+    if MAX_ADAPTIVE_DEPTH - depth >= PARALLEL_LEVELS {
+        return refine(f, whole, halves, target, depth, budget);
+    }
+
+    if let Some(piece) = accept(&whole, &halves, target, depth, budget) {
+        return piece;
+    }
+
+    let (left, right) = halves;
+    let (left, right) = rayon::join(
+        move || par_refine(f, left, left.split(f), 0.5*target, depth - 1, budget),
+        move || par_refine(f, right, right.split(f), 0.5*target, depth - 1, budget),
+    );
+    left.join(right)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adaptive_simpson_cubic_is_exact() {
+        let result = adaptive_simpson(|x| x*x*x, 0.0, 2.0, Tolerance::default()).unwrap();
+
+        assert_eq!(4.0, result.value);
+        assert_eq!(0.0, result.error);
+        assert_eq!(5, result.evaluations);
+        assert!(result.converged());
+    }
+
+    #[test]
+    fn test_adaptive_simpson_meets_tolerance() {
+        let expected = 2.0;
+        let result = adaptive_simpson(f64::sin, 0.0, std::f64::consts::PI, Tolerance::absolute(1e-9)).unwrap();
+
+        assert!((expected - result.value).abs() < 1e-9);
+        assert!(result.error < 1e-9);
+    }
+
+    #[test]
+    fn test_adaptive_simpson_relative_tolerance() {
+        // Integral of 1e6*exp(x) over [0, 1]
+        let expected = 1e6*(1f64.exp() - 1.0);
+        let result = adaptive_simpson(|x| 1e6*x.exp(), 0.0, 1.0, Tolerance::relative(1e-12)).unwrap();
+
+        assert!(((expected - result.value)/expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_adaptive_simpson_concentrates_evaluations() {
+        // A sharp peak at 0.3 needs many more points than a uniform grid
+        // at the same accuracy would spend on the flat parts.
+        let f = |x: f64| 1.0/(1e-4 + (x - 0.3)*(x - 0.3));
+        let expected = 100.0*((0.7f64/0.01).atan() + (0.3f64/0.01).atan());
+        let result = adaptive_simpson(f, 0.0, 1.0, Tolerance::relative(1e-10)).unwrap();
+
+        assert!(((expected - result.value)/expected).abs() < 1e-9);
+        assert!(result.evaluations < 5000);
+    }
+
+    #[test]
+    fn test_adaptive_simpson_unreachable_tolerance() {
+        // A jump can never be resolved to an absolute error of 1e-300
+        let f = |x: f64| if x < 1.0/3.0 { 0.0 } else { 1.0 };
+        let result = adaptive_simpson(f, 0.0, 1.0, Tolerance::absolute(1e-300)).unwrap();
+
+        assert!(!result.converged());
+        assert!((result.value - 2.0/3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_adaptive_simpson_zero_integral_relative_tolerance() {
+        let result = adaptive_simpson(f64::sin, 0.0, 2.0*std::f64::consts::PI, Tolerance::relative(1e-8)).unwrap();
+
+        assert!(result.converged());
+        assert!(result.value.abs() < 1e-12);
+        assert!(result.evaluations < 10_000);
+    }
+
+    #[test]
+    fn test_adaptive_simpson_evaluation_limit() {
+        // Noise can never be resolved, so every interval would be halved to
+        // the full depth without the limit.
+        let f = |x: f64| ((1e7*x).sin()*1e4).fract();
+        let result = adaptive_simpson(f, 0.0, 1.0, Tolerance::absolute(1e-12)).unwrap();
+
+        assert_eq!(Termination::SubdivisionLimit, result.termination);
+        assert!(result.evaluations <= MAX_ADAPTIVE_EVALUATIONS);
+    }
+
+    #[test]
+    fn test_adaptive_simpson_invalid_tolerance() {
+        let expected = Err(IntegrationError::InvalidTolerance);
+        let actual = adaptive_simpson(f64::sin, 0.0, 1.0, Tolerance::absolute(0.0));
+
+        assert_eq!(expected, actual);
+    }
+}
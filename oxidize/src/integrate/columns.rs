@@ -0,0 +1,536 @@
+//! The sample-based rules for data kept in separate columns rather than
+//! in `(x, y)` pairs.
+//!
+//! The `_xy` rules take the abscissae and ordinates as two slices, the
+//! `_dx` rules take evenly-spaced ordinates and their spacing, and the
+//! `_strided` rules take `Strided` views into larger buffers. None of them
+//! copy the data. Like the rules on pairs, each assumes what `trapezoidal`
+//! or `simpson` does and has a `try_` twin that checks it first, along with
+//! the lengths of the columns and, for the `_dx` rules, the spacing. The
+//! plain `_xy` and `_strided` rules panic on columns of different lengths
+//! rather than drop points.
+
+use sum::Summation;
+
+use super::rules;
+use super::samples::{Columns, StridedColumns, Uniform};
+use super::{Float, IntegrationError, Ordinate};
+
+/// A view of every `stride`th element of a slice, starting at `offset`.
+///
+/// Use it to integrate a column of a row-major table, or one channel of
+/// interleaved data, in place.
+///
+/// ```
+/// use oxidize::integrate::{trapezoidal_strided, Strided};
+///
+/// // Interleaved (x, y) samples of y = x
+/// let buffer = [0.0, 0.0, 0.5, 0.5, 1.0, 1.0];
+/// let xs = Strided::new(&buffer, 0, 2).unwrap();
+/// let ys = Strided::new(&buffer, 1, 2).unwrap();
+/// assert_eq!(0.5, trapezoidal_strided(xs, ys));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Strided<'a, T: 'a> {
+    data: &'a [T],
+    offset: usize,
+    stride: usize,
+    len: usize,
+}
+
+impl<'a, T: Copy> Strided<'a, T> {
+    /// Creates a view of `data[offset]`, `data[offset + stride]` and so on
+    /// to the end of `data`.
+    ///
+    /// Fails if `stride` is 0 or `offset` is past the end of `data`.
+    pub fn new(data: &'a [T], offset: usize, stride: usize) -> Result<Strided<'a, T>, IntegrationError> {
+        if stride == 0 {
+            return Err(IntegrationError::InvalidParameter { name: "stride" });
+        }
+        if offset > data.len() {
+            return Err(IntegrationError::InvalidParameter { name: "offset" });
+        }
+
+        let len = (data.len() - offset).div_ceil(stride);
+        Ok(Strided { data, offset, stride, len })
+    }
+
+    /// The number of elements in the view.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the view has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the `i`th element of the view.
+    ///
+    /// Panics if `i` is not less than `len()`.
+    pub fn get(&self, i: usize) -> T {
+        assert!(i < self.len, "index {} out of range for a view of {}", i, self.len);
+        self.data[self.offset + i*self.stride]
+    }
+}
+
+/// Computes an integral using the trapezoid rule over separate abscissae
+/// and ordinates.
+///
+/// Panics if `xs` and `ys` differ in length.
+///
+/// Assumptions:
+/// 1. That the data is evenly-spaced.
+///
+/// ```
+/// use oxidize::integrate::trapezoidal_xy;
+///
+/// let xs = vec![0.0, 0.5, 1.0];
+/// let ys = vec![0.0, 0.5, 1.0];
+/// assert_eq!(0.5, trapezoidal_xy(&xs, &ys));
+/// ```
+pub fn trapezoidal_xy<T: Float, Y: Ordinate<T>>(xs: &[T], ys: &[Y]) -> Y {
+    rules::trapezoidal(&paired(xs, ys), Summation::Naive)
+}
+
+/// Computes an integral using the trapezoid rule with the actual width of
+/// every interval, over separate abscissae and ordinates.
+///
+/// Panics if `xs` and `ys` differ in length.
+pub fn trapezoidal_nonuniform_xy<T: Float, Y: Ordinate<T>>(xs: &[T], ys: &[Y]) -> Y {
+    rules::trapezoidal_nonuniform(&paired(xs, ys), Summation::Naive)
+}
+
+/// Computes an integral using Simpson's rule over separate abscissae and
+/// ordinates.
+///
+/// Panics if `xs` and `ys` differ in length.
+///
+/// Assumptions:
+/// 1. That the data is evenly-spaced.
+/// 2. That there are an odd number of data points (even number of slices).
+pub fn simpson_xy<T: Float, Y: Ordinate<T>>(xs: &[T], ys: &[Y]) -> Y {
+    rules::simpson(&paired(xs, ys), Summation::Naive)
+}
+
+/// Computes an integral using Simpson's 3/8 rule over separate abscissae
+/// and ordinates.
+///
+/// Panics if `xs` and `ys` differ in length.
+///
+/// Assumptions:
+/// 1. That the data is evenly-spaced.
+/// 2. That the number of slices is a multiple of 3.
+pub fn simpson38_xy<T: Float, Y: Ordinate<T>>(xs: &[T], ys: &[Y]) -> Y {
+    rules::simpson38(&paired(xs, ys), Summation::Naive)
+}
+
+/// Computes an integral using Simpson's rules for any number of points
+/// over separate abscissae and ordinates.
+///
+/// Panics if `xs` and `ys` differ in length.
+///
+/// Assumptions:
+/// 1. That the data is evenly-spaced.
+pub fn simpson_auto_xy<T: Float, Y: Ordinate<T>>(xs: &[T], ys: &[Y]) -> Y {
+    rules::simpson_auto(&paired(xs, ys), Summation::Naive)
+}
+
+/// Computes an integral using Simpson's rule with the actual width of
+/// every interval, over separate abscissae and ordinates.
+///
+/// Panics if `xs` and `ys` differ in length.
+///
+/// Assumptions:
+/// 1. That there are an odd number of data points (even number of slices).
+pub fn simpson_nonuniform_xy<T: Float, Y: Ordinate<T>>(xs: &[T], ys: &[Y]) -> Y {
+    rules::simpson_nonuniform(&paired(xs, ys), Summation::Naive)
+}
+
+/// Computes an integral using the trapezoid rule over ordinates sampled
+/// every `dx`.
+///
+/// ```
+/// use oxidize::integrate::trapezoidal_dx;
+///
+/// assert_eq!(0.5, trapezoidal_dx(0.5, &[0.0, 0.5, 1.0]));
+/// ```
+pub fn trapezoidal_dx<T: Float, Y: Ordinate<T>>(dx: T, ys: &[Y]) -> Y {
+    rules::trapezoidal(&Uniform { dx, ys }, Summation::Naive)
+}
+
+/// Computes an integral using Simpson's rule over ordinates sampled every
+/// `dx`.
+///
+/// Assumptions:
+/// 1. That there are an odd number of ordinates (even number of slices).
+pub fn simpson_dx<T: Float, Y: Ordinate<T>>(dx: T, ys: &[Y]) -> Y {
+    rules::simpson(&Uniform { dx, ys }, Summation::Naive)
+}
+
+/// Computes an integral using Simpson's 3/8 rule over ordinates sampled
+/// every `dx`.
+///
+/// Assumptions:
+/// 1. That the number of slices is a multiple of 3.
+pub fn simpson38_dx<T: Float, Y: Ordinate<T>>(dx: T, ys: &[Y]) -> Y {
+    rules::simpson38(&Uniform { dx, ys }, Summation::Naive)
+}
+
+/// Computes an integral using Simpson's rules for any number of ordinates
+/// sampled every `dx`.
+pub fn simpson_auto_dx<T: Float, Y: Ordinate<T>>(dx: T, ys: &[Y]) -> Y {
+    rules::simpson_auto(&Uniform { dx, ys }, Summation::Naive)
+}
+
+/// Computes an integral using the trapezoid rule over strided views of the
+/// abscissae and ordinates.
+///
+/// Panics if `xs` and `ys` differ in length.
+///
+/// Assumptions:
+/// 1. That the data is evenly-spaced.
+pub fn trapezoidal_strided<T: Float, Y: Ordinate<T>>(xs: Strided<T>, ys: Strided<Y>) -> Y {
+    rules::trapezoidal(&paired_strided(xs, ys), Summation::Naive)
+}
+
+/// Computes an integral using Simpson's rule over strided views of the
+/// abscissae and ordinates.
+///
+/// Panics if `xs` and `ys` differ in length.
+///
+/// Assumptions:
+/// 1. That the data is evenly-spaced.
+/// 2. That there are an odd number of data points (even number of slices).
+pub fn simpson_strided<T: Float, Y: Ordinate<T>>(xs: Strided<T>, ys: Strided<Y>) -> Y {
+    rules::simpson(&paired_strided(xs, ys), Summation::Naive)
+}
+
+/// Computes an integral using the trapezoid rule over separate abscissae
+/// and ordinates, checking the assumptions of `trapezoidal_xy` first.
+///
+/// Fails if `xs` and `ys` differ in length, or as `try_trapezoidal` does.
+///
+/// ```
+/// use oxidize::integrate::{try_trapezoidal_xy, IntegrationError};
+///
+/// let xs = vec![0.0, 0.5, 1.0];
+/// assert_eq!(Ok(0.5), try_trapezoidal_xy(&xs, &[0.0, 0.5, 1.0]));
+/// assert_eq!(Err(IntegrationError::LengthMismatch { expected: 3, actual: 2 }), try_trapezoidal_xy(&xs, &[0.0, 0.5]));
+/// ```
+pub fn try_trapezoidal_xy<T: Float, Y: Ordinate<T>>(xs: &[T], ys: &[Y]) -> Result<Y, IntegrationError> {
+    rules::try_trapezoidal(&columns(xs, ys)?)
+}
+
+/// Computes an integral using the trapezoid rule with the actual width of
+/// every interval, over separate abscissae and ordinates, checking the
+/// assumptions of `trapezoidal_nonuniform_xy` first.
+///
+/// Fails if `xs` and `ys` differ in length, or as
+/// `try_trapezoidal_nonuniform` does.
+pub fn try_trapezoidal_nonuniform_xy<T: Float, Y: Ordinate<T>>(xs: &[T], ys: &[Y]) -> Result<Y, IntegrationError> {
+    rules::try_trapezoidal_nonuniform(&columns(xs, ys)?)
+}
+
+/// Computes an integral using Simpson's rule over separate abscissae and
+/// ordinates, checking the assumptions of `simpson_xy` first.
+///
+/// Fails if `xs` and `ys` differ in length, or as `try_simpson` does.
+pub fn try_simpson_xy<T: Float, Y: Ordinate<T>>(xs: &[T], ys: &[Y]) -> Result<Y, IntegrationError> {
+    rules::try_simpson(&columns(xs, ys)?)
+}
+
+/// Computes an integral using Simpson's 3/8 rule over separate abscissae
+/// and ordinates, checking the assumptions of `simpson38_xy` first.
+///
+/// Fails if `xs` and `ys` differ in length, or as `try_simpson38` does.
+pub fn try_simpson38_xy<T: Float, Y: Ordinate<T>>(xs: &[T], ys: &[Y]) -> Result<Y, IntegrationError> {
+    rules::try_simpson38(&columns(xs, ys)?)
+}
+
+/// Computes an integral using Simpson's rules for any number of points
+/// over separate abscissae and ordinates, checking the assumptions of
+/// `simpson_auto_xy` first.
+///
+/// Fails if `xs` and `ys` differ in length, or as `try_simpson_auto` does.
+pub fn try_simpson_auto_xy<T: Float, Y: Ordinate<T>>(xs: &[T], ys: &[Y]) -> Result<Y, IntegrationError> {
+    rules::try_simpson_auto(&columns(xs, ys)?)
+}
+
+/// Computes an integral using Simpson's rule with the actual width of
+/// every interval, over separate abscissae and ordinates, checking the
+/// assumptions of `simpson_nonuniform_xy` first.
+///
+/// Fails if `xs` and `ys` differ in length, or as `try_simpson_nonuniform`
+/// does.
+pub fn try_simpson_nonuniform_xy<T: Float, Y: Ordinate<T>>(xs: &[T], ys: &[Y]) -> Result<Y, IntegrationError> {
+    rules::try_simpson_nonuniform(&columns(xs, ys)?)
+}
+
+/// Computes an integral using the trapezoid rule over ordinates sampled
+/// every `dx`, checking the assumptions of `trapezoidal_dx` first.
+///
+/// Fails if `dx` is not positive and finite, if there are fewer than 2
+/// ordinates or if any ordinate is not finite.
+pub fn try_trapezoidal_dx<T: Float, Y: Ordinate<T>>(dx: T, ys: &[Y]) -> Result<Y, IntegrationError> {
+    rules::try_trapezoidal(&uniform(dx, ys)?)
+}
+
+/// Computes an integral using Simpson's rule over ordinates sampled every
+/// `dx`, checking the assumptions of `simpson_dx` first.
+///
+/// Fails if `dx` is not positive and finite, if there are fewer than 3
+/// ordinates or an even number of them, or if any ordinate is not finite.
+pub fn try_simpson_dx<T: Float, Y: Ordinate<T>>(dx: T, ys: &[Y]) -> Result<Y, IntegrationError> {
+    rules::try_simpson(&uniform(dx, ys)?)
+}
+
+/// Computes an integral using Simpson's 3/8 rule over ordinates sampled
+/// every `dx`, checking the assumptions of `simpson38_dx` first.
+///
+/// Fails if `dx` is not positive and finite, if there are fewer than 4
+/// ordinates or the number of slices is not a multiple of 3, or if any
+/// ordinate is not finite.
+pub fn try_simpson38_dx<T: Float, Y: Ordinate<T>>(dx: T, ys: &[Y]) -> Result<Y, IntegrationError> {
+    rules::try_simpson38(&uniform(dx, ys)?)
+}
+
+/// Computes an integral using Simpson's rules for any number of ordinates
+/// sampled every `dx`, checking the assumptions of `simpson_auto_dx` first.
+///
+/// Fails if `dx` is not positive and finite, if there are fewer than 3
+/// ordinates or if any ordinate is not finite.
+pub fn try_simpson_auto_dx<T: Float, Y: Ordinate<T>>(dx: T, ys: &[Y]) -> Result<Y, IntegrationError> {
+    rules::try_simpson_auto(&uniform(dx, ys)?)
+}
+
+/// Computes an integral using the trapezoid rule over strided views of the
+/// abscissae and ordinates, checking the assumptions of
+/// `trapezoidal_strided` first.
+///
+/// Fails if `xs` and `ys` differ in length, or as `try_trapezoidal` does.
+pub fn try_trapezoidal_strided<T: Float, Y: Ordinate<T>>(xs: Strided<T>, ys: Strided<Y>) -> Result<Y, IntegrationError> {
+    rules::try_trapezoidal(&strided(xs, ys)?)
+}
+
+/// Computes an integral using Simpson's rule over strided views of the
+/// abscissae and ordinates, checking the assumptions of `simpson_strided`
+/// first.
+///
+/// Fails if `xs` and `ys` differ in length, or as `try_simpson` does.
+pub fn try_simpson_strided<T: Float, Y: Ordinate<T>>(xs: Strided<T>, ys: Strided<Y>) -> Result<Y, IntegrationError> {
+    rules::try_simpson(&strided(xs, ys)?)
+}
+
+/// Pairs up columns of the same length.
+///
+/// Panics if `xs` and `ys` differ in length.
+pub fn paired<'a, T, Y>(xs: &'a [T], ys: &'a [Y]) -> Columns<'a, T, Y> {
+    assert_eq!(xs.len(), ys.len(), "xs and ys differ in length");
+    Columns { xs, ys }
+}
+
+/// Pairs up strided columns of the same length.
+///
+/// Panics if `xs` and `ys` differ in length.
+fn paired_strided<'a, T: Copy, Y: Copy>(xs: Strided<'a, T>, ys: Strided<'a, Y>) -> StridedColumns<'a, T, Y> {
+    assert_eq!(xs.len(), ys.len(), "xs and ys differ in length");
+    StridedColumns { xs, ys }
+}
+
+fn columns<'a, T, Y>(xs: &'a [T], ys: &'a [Y]) -> Result<Columns<'a, T, Y>, IntegrationError> {
+    if xs.len() != ys.len() {
+        return Err(IntegrationError::LengthMismatch { expected: xs.len(), actual: ys.len() });
+    }
+
+    Ok(Columns { xs, ys })
+}
+
+/// Takes ordinates sampled every `dx`, checking that `dx` is positive and
+/// finite.
+pub fn uniform<T: Float, Y>(dx: T, ys: &[Y]) -> Result<Uniform<'_, T, Y>, IntegrationError> {
+    if !(dx > T::zero() && dx.is_finite()) {
+        return Err(IntegrationError::InvalidParameter { name: "dx" });
+    }
+
+    Ok(Uniform { dx, ys })
+}
+
+fn strided<'a, T: Copy, Y: Copy>(xs: Strided<'a, T>, ys: Strided<'a, Y>) -> Result<StridedColumns<'a, T, Y>, IntegrationError> {
+    if xs.len() != ys.len() {
+        return Err(IntegrationError::LengthMismatch { expected: xs.len(), actual: ys.len() });
+    }
+
+    Ok(StridedColumns { xs, ys })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use integrate::{simpson, simpson_nonuniform, trapezoidal};
+    use integrate::{try_simpson, try_simpson38, try_simpson_auto, try_trapezoidal};
+
+    fn sin_squared() -> (Vec<f64>, Vec<f64>) {
+        let xs: Vec<f64> = (0..9).map(|i| 0.125*i as f64).collect();
+        let ys = xs.iter().map(|x| x.sin()*x.sin()).collect();
+
+        (xs, ys)
+    }
+
+    #[test]
+    fn test_trapezoidal_xy_matches_pairs() {
+        let (xs, ys) = sin_squared();
+        let pairs: Vec<(f64,f64)> = xs.iter().cloned().zip(ys.iter().cloned()).collect();
+
+        let expected = trapezoidal(&pairs);
+
+        assert_eq!(expected, trapezoidal_xy(&xs, &ys));
+        assert_eq!(Ok(expected), try_trapezoidal_xy(&xs, &ys));
+    }
+
+    #[test]
+    fn test_simpson_nonuniform_xy_matches_pairs() {
+        let xs = [0.0, 0.1, 0.4, 0.5, 1.0];
+        let ys = [1.0, 2.0, 0.5, 3.0, 2.0];
+        let pairs: Vec<(f64,f64)> = xs.iter().cloned().zip(ys.iter().cloned()).collect();
+
+        let expected = simpson_nonuniform(&pairs);
+
+        assert_eq!(expected, simpson_nonuniform_xy(&xs, &ys));
+        assert_eq!(Ok(expected), try_simpson_nonuniform_xy(&xs, &ys));
+    }
+
+    #[test]
+    fn test_try_simpson_xy_length_mismatch() {
+        let expected = Err(IntegrationError::LengthMismatch { expected: 3, actual: 2 });
+        let actual = try_simpson_xy(&[0.0, 1.0, 2.0], &[0.0, 1.0]);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    #[should_panic(expected = "xs and ys differ in length")]
+    fn test_trapezoidal_xy_length_mismatch_panics() {
+        let xs = [0.0, 1.0, 2.0, 3.0];
+        let ys = [1.0, 1.0, 1.0];
+
+        trapezoidal_xy(&xs, &ys);
+    }
+
+    #[test]
+    fn test_simpson_dx_matches_pairs() {
+        let (xs, ys) = sin_squared();
+        let pairs: Vec<(f64,f64)> = xs.iter().cloned().zip(ys.iter().cloned()).collect();
+
+        let expected = simpson(&pairs);
+
+        assert_eq!(expected, simpson_dx(0.125, &ys));
+        assert_eq!(Ok(expected), try_simpson_dx(0.125, &ys));
+    }
+
+    #[test]
+    fn test_trapezoidal_dx_many_points() {
+        // Abscissae computed as i*dx drift too far from even spacing for the
+        // check on columns, but the spacing of `dx` data is exact by
+        // construction.
+        let n = 100_001;
+        let dx = 1e-5f32;
+        let xs: Vec<f32> = (0..n).map(|i| i as f32*dx).collect();
+        let ys = vec![1.0f32; n];
+
+        let actual = try_trapezoidal_dx(dx, &ys).unwrap();
+
+        assert!(try_trapezoidal_xy(&xs, &ys).is_err());
+        assert!((actual - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_try_simpson_dx_invalid_spacing() {
+        let expected = Err(IntegrationError::InvalidParameter { name: "dx" });
+
+        assert_eq!(expected, try_simpson_dx(0.0, &[0.0, 1.0, 2.0]));
+        assert_eq!(expected, try_simpson_dx(f64::NAN, &[0.0, 1.0, 2.0]));
+    }
+
+    #[test]
+    fn test_try_dx_errors_match_pairs() {
+        // The `_dx` rules build their abscissae from `dx` but must fail as
+        // the rules on pairs do.
+        let dx = 0.5;
+        let cases: Vec<Vec<f64>> = vec![
+            vec![],
+            vec![f64::NAN],
+            vec![1.0, f64::NAN],
+            vec![1.0, 2.0, f64::INFINITY, 4.0],
+            vec![f64::NAN, 2.0, f64::NEG_INFINITY, 4.0, 5.0],
+            vec![1.0, 2.0, 3.0, 4.0, f64::INFINITY, 6.0, 7.0],
+            vec![1e308, 1e308, 1e308],
+        ];
+
+        for ys in &cases {
+            let pairs: Vec<(f64,f64)> = ys.iter().enumerate().map(|(i, &y)| (i as f64*dx, y)).collect();
+
+            assert_eq!(try_trapezoidal(&pairs), try_trapezoidal_dx(dx, ys), "{:?}", ys);
+            assert_eq!(try_simpson(&pairs), try_simpson_dx(dx, ys), "{:?}", ys);
+            assert_eq!(try_simpson38(&pairs), try_simpson38_dx(dx, ys), "{:?}", ys);
+            assert_eq!(try_simpson_auto(&pairs), try_simpson_auto_dx(dx, ys), "{:?}", ys);
+        }
+    }
+
+    #[test]
+    fn test_dx_too_few_points_is_zero() {
+        assert_eq!(0.0, trapezoidal_dx(0.5, &[1.0]));
+        assert_eq!(0.0, simpson_dx(0.5, &[1.0]));
+        assert_eq!(0.0, simpson38_dx(0.5, &[] as &[f64]));
+        assert_eq!(0.0, simpson_auto_dx(0.5, &[1.0, 2.0]));
+    }
+
+    #[test]
+    fn test_strided_column_of_table() {
+        // Rows of (t, x, y); integrate y over t
+        let table = [
+            0.0, 9.0, 0.0,
+            0.5, 9.0, 0.25,
+            1.0, 9.0, 1.0,
+        ];
+        let ts = Strided::new(&table, 0, 3).unwrap();
+        let ys = Strided::new(&table, 2, 3).unwrap();
+
+        let expected = 1.0/3.0;
+
+        assert_eq!(expected, simpson_strided(ts, ys));
+        assert_eq!(Ok(expected), try_simpson_strided(ts, ys));
+    }
+
+    #[test]
+    fn test_strided_len() {
+        let data = [0.0; 7];
+
+        assert_eq!(4, Strided::new(&data, 0, 2).unwrap().len());
+        assert_eq!(3, Strided::new(&data, 1, 2).unwrap().len());
+        assert!(Strided::new(&data, 7, 2).unwrap().is_empty());
+        assert_eq!(Err(IntegrationError::InvalidParameter { name: "stride" }), Strided::new(&data, 0, 0));
+    }
+
+    #[test]
+    fn test_try_trapezoidal_strided_length_mismatch() {
+        let data = [0.0, 1.0, 2.0, 3.0, 4.0];
+        let xs = Strided::new(&data, 0, 2).unwrap();
+        let ys = Strided::new(&data, 1, 2).unwrap();
+
+        let expected = Err(IntegrationError::LengthMismatch { expected: 3, actual: 2 });
+        let actual = try_trapezoidal_strided(xs, ys);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    #[should_panic(expected = "xs and ys differ in length")]
+    fn test_simpson_strided_length_mismatch_panics() {
+        let data = [0.0, 1.0, 2.0, 3.0, 4.0];
+        let xs = Strided::new(&data, 0, 2).unwrap();
+        let ys = Strided::new(&data, 1, 2).unwrap();
+
+        simpson_strided(xs, ys);
+    }
+}
@@ -0,0 +1,181 @@
+//! Complex numbers, so the rules can integrate complex-valued signals.
+
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+use super::{Float, Ordinate};
+
+/// A complex number `re + i*im`.
+///
+/// Only the arithmetic the integration rules and typical integrands need
+/// is provided; the transcendental functions are implemented for
+/// `Complex<f64>`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Complex<T> {
+    /// The real part.
+    pub re: T,
+
+    /// The imaginary part.
+    pub im: T,
+}
+
+impl<T: Float> Complex<T> {
+    /// Creates `re + i*im`.
+    pub fn new(re: T, im: T) -> Complex<T> {
+        Complex { re, im }
+    }
+
+    /// Returns `i`.
+    pub fn i() -> Complex<T> {
+        Complex::new(T::zero(), T::from_f64(1.0))
+    }
+
+    /// Returns the complex conjugate.
+    pub fn conj(self) -> Complex<T> {
+        Complex::new(self.re, -self.im)
+    }
+
+    /// Returns the squared magnitude.
+    pub fn norm_sqr(self) -> T {
+        self.re*self.re + self.im*self.im
+    }
+
+    /// Returns the magnitude.
+    pub fn norm(self) -> T {
+        self.re.hypot(self.im)
+    }
+}
+
+impl Complex<f64> {
+    /// Creates the complex number with magnitude `r` and argument `theta`.
+    pub fn from_polar(r: f64, theta: f64) -> Complex<f64> {
+        Complex::new(r*theta.cos(), r*theta.sin())
+    }
+
+    /// Returns the argument, in `(-pi, pi]`.
+    pub fn arg(self) -> f64 {
+        self.im.atan2(self.re)
+    }
+
+    /// Returns `e` raised to this number.
+    pub fn exp(self) -> Complex<f64> {
+        Complex::from_polar(self.re.exp(), self.im)
+    }
+}
+
+impl<T: Float> From<T> for Complex<T> {
+    fn from(re: T) -> Complex<T> {
+        Complex::new(re, T::zero())
+    }
+}
+
+impl<T: Float> Add for Complex<T> {
+    type Output = Complex<T>;
+
+    fn add(self, other: Complex<T>) -> Complex<T> {
+        Complex::new(self.re + other.re, self.im + other.im)
+    }
+}
+
+impl<T: Float> Sub for Complex<T> {
+    type Output = Complex<T>;
+
+    fn sub(self, other: Complex<T>) -> Complex<T> {
+        Complex::new(self.re - other.re, self.im - other.im)
+    }
+}
+
+impl<T: Float> Mul for Complex<T> {
+    type Output = Complex<T>;
+
+    fn mul(self, other: Complex<T>) -> Complex<T> {
+        Complex::new(
+            self.re*other.re - self.im*other.im,
+            self.re*other.im + self.im*other.re,
+        )
+    }
+}
+
+impl<T: Float> Div for Complex<T> {
+    type Output = Complex<T>;
+
+    fn div(self, other: Complex<T>) -> Complex<T> {
+        let denominator = other.norm_sqr();
+        let numerator = self*other.conj();
+
+        Complex::new(numerator.re/denominator, numerator.im/denominator)
+    }
+}
+
+impl<T: Float> Mul<T> for Complex<T> {
+    type Output = Complex<T>;
+
+    fn mul(self, scale: T) -> Complex<T> {
+        Complex::new(self.re*scale, self.im*scale)
+    }
+}
+
+impl<T: Float> Div<T> for Complex<T> {
+    type Output = Complex<T>;
+
+    fn div(self, scale: T) -> Complex<T> {
+        Complex::new(self.re/scale, self.im/scale)
+    }
+}
+
+impl<T: Float> Neg for Complex<T> {
+    type Output = Complex<T>;
+
+    fn neg(self) -> Complex<T> {
+        Complex::new(-self.re, -self.im)
+    }
+}
+
+impl<T: Float> AddAssign for Complex<T> {
+    fn add_assign(&mut self, other: Complex<T>) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Float> SubAssign for Complex<T> {
+    fn sub_assign(&mut self, other: Complex<T>) {
+        *self = *self - other;
+    }
+}
+
+impl<T: Float> Ordinate<T> for Complex<T> {
+    fn zero() -> Complex<T> {
+        Complex::new(T::zero(), T::zero())
+    }
+
+    fn is_finite(self) -> bool {
+        self.re.is_finite() && self.im.is_finite()
+    }
+
+    fn norm(self) -> T {
+        Complex::norm(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_complex_division_inverts_multiplication() {
+        let a = Complex::new(3.0, -2.0);
+        let b = Complex::new(0.5, 4.0);
+
+        let expected = a;
+        let actual = a*b/b;
+
+        assert!((expected - actual).norm() < 1e-15);
+    }
+
+    #[test]
+    fn test_complex_exp_euler() {
+        let expected = Complex::new(-1.0, 0.0);
+        let actual = (Complex::i()*std::f64::consts::PI).exp();
+
+        assert!((expected - actual).norm() < 1e-15);
+    }
+}
@@ -0,0 +1,234 @@
+//! Integrals of complex-valued functions, along the real line and along
+//! paths in the complex plane.
+
+#[cfg(feature = "parallel")]
+use super::qags::Parallel;
+use super::qags::{self, Solver};
+use super::{Complex, IntegrationError, QuadOptions, QuadratureResult};
+
+/// Computes the integral of a complex-valued `f` over `[a, b]` with
+/// globally adaptive Gauss–Kronrod quadrature, as `quad_with` does.
+///
+/// The real and imaginary parts are refined together on one set of
+/// subintervals, so `f` is evaluated once at every point. Errors are
+/// measured with the modulus: a subinterval is bisected while the modulus
+/// of its error estimate is among the largest, and the tolerance applies to
+/// the modulus of the result.
+///
+/// Fails as `quad_with` does, or if either part of `f` is NaN or infinite
+/// at a point it is evaluated at.
+///
+/// ```
+/// use oxidize::integrate::{quad_complex, Complex, QuadOptions};
+///
+/// // The first Fourier coefficient of a square wave
+/// let f = |t: f64| {
+///     let square = if t < std::f64::consts::PI { 1.0 } else { -1.0 };
+///     Complex::from_polar(square, -t)
+/// };
+/// let result = quad_complex(f, 0.0, 2.0*std::f64::consts::PI, QuadOptions::default()).unwrap();
+/// assert!((result.value - Complex::new(0.0, -4.0)).norm() < 1e-9);
+/// ```
+pub fn quad_complex<F: Fn(f64) -> Complex<f64>>(f: F, a: f64, b: f64, options: QuadOptions) -> Result<QuadratureResult<Complex<f64>>, IntegrationError> {
+    qags::solve(&f, a, b, options, |integrand, a, b| Solver::new(integrand, a, b, options).run())
+}
+
+/// Computes an integral as `quad_complex` does, evaluating the integrand
+/// on several threads.
+///
+/// The values are combined in the same order as `quad_complex` combines
+/// them, so the result is the same.
+///
+/// Fails as `quad_complex` does.
+#[cfg(feature = "parallel")]
+pub fn par_quad_complex<F: Fn(f64) -> Complex<f64> + Sync>(f: F, a: f64, b: f64, options: QuadOptions) -> Result<QuadratureResult<Complex<f64>>, IntegrationError> {
+    qags::solve(&f, a, b, options, |integrand, a, b| Solver::new(&Parallel(integrand), a, b, options).run())
+}
+
+/// Computes the integral of `f` along the path `z(t)` for `t` in `[a, b]`.
+///
+/// `derivative` is `z'(t)`, and the integral of `f(z(t))*z'(t)` over
+/// `[a, b]` is computed with `quad_complex`. A closed contour is
+/// traversed once when `z(a)` equals `z(b)`, in the direction of
+/// increasing `t`.
+///
+/// Fails as `quad_with` does.
+///
+/// ```
+/// use oxidize::integrate::{contour, Complex, QuadOptions};
+///
+/// // The unit circle, anticlockwise, around the pole of 1/z
+/// let circle = |t: f64| Complex::from_polar(1.0, t);
+/// let tangent = |t: f64| Complex::i()*Complex::from_polar(1.0, t);
+/// let f = |z: Complex<f64>| Complex::from(1.0)/z;
+///
+/// let two_pi = 2.0*std::f64::consts::PI;
+/// let result = contour(f, circle, tangent, 0.0, two_pi, QuadOptions::default()).unwrap();
+/// assert!((result.value - Complex::new(0.0, two_pi)).norm() < 1e-10);
+/// ```
+pub fn contour<F, Z, D>(f: F, path: Z, derivative: D, a: f64, b: f64, options: QuadOptions) -> Result<QuadratureResult<Complex<f64>>, IntegrationError>
+    where F: Fn(Complex<f64>) -> Complex<f64>,
+          Z: Fn(f64) -> Complex<f64>,
+          D: Fn(f64) -> Complex<f64>
+{
+    quad_complex(|t| f(path(t))*derivative(t), a, b, options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use integrate::{adaptive_simpson, gauss_laguerre, gauss_legendre, quad, romberg, simpson, simpson_fn, tanh_sinh};
+    use integrate::{trapezoidal, try_simpson, RombergOptions, TanhSinhOptions, Tolerance};
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_trapezoidal_complex_fourier_coefficient() {
+        // The trapezoid rule is exact for trigonometric polynomials over a
+        // whole period: the coefficient of e^(2it) in cos(2t) is 1/2.
+        let n = 16;
+        let data: Vec<(f64, Complex<f64>)> = (0..n + 1)
+            .map(|k| {
+                let t = 2.0*PI*k as f64/n as f64;
+                (t, Complex::from_polar((2.0*t).cos(), -2.0*t))
+            })
+            .collect();
+
+        let expected = Complex::new(0.5, 0.0);
+        let actual = trapezoidal(&data)*(1.0/(2.0*PI));
+
+        assert!((expected - actual).norm() < 1e-15);
+    }
+
+    #[test]
+    fn test_simpson_complex_matches_parts() {
+        let data: Vec<(f64, Complex<f64>)> = (0..9)
+            .map(|k| {
+                let x = 0.25*k as f64;
+                (x, Complex::new(x*x, x.sin()))
+            })
+            .collect();
+        let re: Vec<(f64, f64)> = data.iter().map(|&(x, y)| (x, y.re)).collect();
+        let im: Vec<(f64, f64)> = data.iter().map(|&(x, y)| (x, y.im)).collect();
+
+        let expected = Complex::new(simpson(&re), simpson(&im));
+        let actual = simpson(&data);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_try_simpson_complex_non_finite() {
+        let data = [
+            (0.0, Complex::new(0.0, 0.0)),
+            (1.0, Complex::new(1.0, f64::NAN)),
+            (2.0, Complex::new(2.0, 0.0)),
+        ];
+
+        let expected = Err(IntegrationError::NonFinite { index: 1 });
+        let actual = try_simpson(&data);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_simpson_fn_complex_exponential() {
+        // Integral of e^(ix) over [0, pi/2] is 1 + i
+        let expected = Complex::new(1.0, 1.0);
+        let actual = simpson_fn(|x| Complex::from_polar(1.0, x), 0.0, 0.5*PI, 64).unwrap();
+
+        assert!((expected - actual).norm() < 1e-8);
+    }
+
+    #[test]
+    fn test_quad_complex_evaluations() {
+        let result = quad_complex(|x| Complex::new(x, 2.0*x), 0.0, 1.0, QuadOptions::default()).unwrap();
+
+        // One Gauss-Kronrod rule covers both parts
+        assert!((result.value - Complex::new(0.5, 1.0)).norm() < 1e-15);
+        assert_eq!(21, result.evaluations);
+        assert!(result.converged());
+    }
+
+    #[test]
+    fn test_quad_complex_singularity_matches_parts() {
+        let f = |x: f64| Complex::from_polar(1.0/x.sqrt(), x);
+        let re = quad(|x| f(x).re, 0.0, 1.0).unwrap();
+        let im = quad(|x| f(x).im, 0.0, 1.0).unwrap();
+        let result = quad_complex(f, 0.0, 1.0, QuadOptions::default()).unwrap();
+
+        assert!(result.converged());
+        assert!((result.value - Complex::new(re.value, im.value)).norm() < 1e-9);
+        assert!(result.evaluations < re.evaluations + im.evaluations);
+    }
+
+    #[test]
+    fn test_quad_complex_non_finite() {
+        let f = |x: f64| Complex::new(x, if x > 0.5 { f64::NAN } else { 0.0 });
+        let result = quad_complex(f, 0.0, 1.0, QuadOptions::default());
+
+        assert_eq!(Err(IntegrationError::NonFiniteIntegrand), result);
+    }
+
+    #[test]
+    fn test_adaptive_simpson_complex_exponential() {
+        // Integral of e^(ix) over [0, pi/2] is 1 + i
+        let expected = Complex::new(1.0, 1.0);
+        let result = adaptive_simpson(|x| Complex::from_polar(1.0, x), 0.0, 0.5*PI, Tolerance::absolute(1e-12)).unwrap();
+
+        assert!(result.converged());
+        assert!((expected - result.value).norm() < 1e-12);
+    }
+
+    #[test]
+    fn test_romberg_complex_exponential() {
+        let expected = Complex::new(1.0, 1.0);
+        let result = romberg(|x| Complex::from_polar(1.0, x), 0.0, 0.5*PI, RombergOptions::default()).unwrap();
+
+        assert!(result.converged);
+        assert!((expected - result.value).norm() < 1e-12);
+        assert_eq!(result.value, result.tableau[result.tableau.len() - 1][result.tableau.len() - 1]);
+    }
+
+    #[test]
+    fn test_gauss_rules_complex_match_parts() {
+        let f = |x: f64| Complex::new(x.exp(), (-x*x).exp());
+        let re = gauss_legendre(|x| f(x).re, 0.0, 1.0, 8).unwrap();
+        let im = gauss_legendre(|x| f(x).im, 0.0, 1.0, 8).unwrap();
+
+        assert_eq!(Complex::new(re, im), gauss_legendre(f, 0.0, 1.0, 8).unwrap());
+
+        // The integral of x e^(ix) e^(-x) over [0, inf) is 1/(1 - i)^2 = i/2
+        let laguerre = gauss_laguerre(|x| Complex::from_polar(x, x), 30).unwrap();
+        assert!((laguerre - Complex::new(0.0, 0.5)).norm() < 1e-10);
+    }
+
+    #[test]
+    fn test_tanh_sinh_complex_singularity() {
+        // Integral of (1 + i)/sqrt(x) over [0, 1] is 2 + 2i
+        let expected = Complex::new(2.0, 2.0);
+        let result = tanh_sinh(|x: f64| Complex::new(1.0, 1.0)*(1.0/x.sqrt()), 0.0, 1.0, TanhSinhOptions::default()).unwrap();
+
+        assert!(result.converged());
+        assert!((expected - result.value).norm() < 1e-12);
+    }
+
+    #[test]
+    fn test_contour_analytic_is_zero() {
+        // Cauchy's theorem: z^2 has no poles, so the closed integral vanishes
+        let circle = |t: f64| Complex::new(1.0, 0.5) + Complex::from_polar(2.0, t);
+        let tangent = |t: f64| Complex::i()*Complex::from_polar(2.0, t);
+
+        let result = contour(|z| z*z, circle, tangent, 0.0, 2.0*PI, QuadOptions::default()).unwrap();
+
+        assert!(result.value.norm() < 1e-10);
+    }
+
+    #[test]
+    fn test_contour_segment() {
+        // Integral of z along the straight line from 0 to 1 + i is (1 + i)^2/2 = i
+        let end = Complex::new(1.0, 1.0);
+        let result = contour(|z| z, |t| end*t, |_| end, 0.0, 1.0, QuadOptions::default()).unwrap();
+
+        assert!((result.value - Complex::i()).norm() < 1e-14);
+    }
+}
@@ -0,0 +1,256 @@
+//! Running integrals, from the first sample to every sample.
+
+use super::{validate, Float, IntegrationError, Ordinate};
+
+/// Computes the integral from the first point to every point using the
+/// trapezoid rule with the actual width of every interval.
+///
+/// The result has one value per point and starts with `0.0`; its last
+/// value is `trapezoidal_nonuniform(data)`.
+///
+/// ```
+/// use oxidize::integrate::cumulative_trapezoidal;
+///
+/// // Constant acceleration of 2 gives velocity 2t
+/// let acceleration = [(0.0, 2.0), (0.5, 2.0), (2.0, 2.0)];
+/// assert_eq!(vec![0.0, 1.0, 4.0], cumulative_trapezoidal(&acceleration));
+/// ```
+pub fn cumulative_trapezoidal<T: Float, Y: Ordinate<T>>(data: &[(T,Y)]) -> Vec<Y> {
+    let mut result = Vec::with_capacity(data.len());
+    if data.is_empty() {
+        return result;
+    }
+
+    let mut total = Y::zero();
+    result.push(total);
+
+    for pair in data.windows(2) {
+        total += (pair[0].1 + pair[1].1)*(T::from_f64(0.5)*(pair[1].0 - pair[0].0));
+        result.push(total);
+    }
+
+    result
+}
+
+/// Computes the integral from the first point to every point using
+/// Simpson's rule, which may be unevenly spaced.
+///
+/// Each pair of intervals is integrated piece by piece under the quadratic
+/// through its three points, so at every other point the result agrees
+/// with `simpson_nonuniform`. With an even number of points the last
+/// interval uses the quadratic through the last three points, and with
+/// only two points the trapezoid rule is used.
+///
+/// The result has one value per point and starts with `0.0`.
+pub fn cumulative_simpson<T: Float, Y: Ordinate<T>>(data: &[(T,Y)]) -> Vec<Y> {
+    if data.len() <= 2 {
+        return cumulative_trapezoidal(data);
+    }
+
+    let mut result = Vec::with_capacity(data.len());
+    let mut total = Y::zero();
+    result.push(total);
+
+    for i in 0..data.len() - 1 {
+        if i.is_multiple_of(2) && i + 2 < data.len() {
+            total += first_interval(&data[i..i + 3]);
+        } else {
+            total += second_interval(&data[i - 1..i + 2]);
+        }
+        result.push(total);
+    }
+
+    result
+}
+
+/// Integrates the quadratic through three points over the first interval.
+fn first_interval<T: Float, Y: Ordinate<T>>(panel: &[(T,Y)]) -> Y {
+    let h0 = panel[1].0 - panel[0].0;
+    let h1 = panel[2].0 - panel[1].0;
+    let sum = h0 + h1;
+    let half = T::from_f64(0.5);
+    let two = T::from_f64(2.0);
+    let three = T::from_f64(3.0);
+    let six = T::from_f64(6.0);
+
+    let mut result = Y::zero();
+    result += panel[0].1*(half*h0 - h0*h0/(six*sum));
+    result += panel[1].1*(h0*(three*sum - two*h0)/(six*h1));
+    result -= panel[2].1*(h0*h0*h0/(six*sum*h1));
+
+    result
+}
+
+/// Integrates the quadratic through three points over the second interval.
+pub fn second_interval<T: Float, Y: Ordinate<T>>(panel: &[(T,Y)]) -> Y {
+    let h0 = panel[1].0 - panel[0].0;
+    let h1 = panel[2].0 - panel[1].0;
+    let sum = h0 + h1;
+    let half = T::from_f64(0.5);
+    let two = T::from_f64(2.0);
+    let three = T::from_f64(3.0);
+    let six = T::from_f64(6.0);
+
+    let mut result = Y::zero();
+    result -= panel[0].1*(h1*h1*h1/(six*sum*h0));
+    result += panel[1].1*(h1*(three*sum - two*h1)/(six*h0));
+    result += panel[2].1*(half*h1 - h1*h1/(six*sum));
+
+    result
+}
+
+/// Computes running integrals with `cumulative_trapezoidal`, checking its
+/// input first.
+///
+/// Fails if there are fewer than 2 points, if any coordinate is not finite
+/// or if the abscissae are not strictly increasing.
+pub fn try_cumulative_trapezoidal<T: Float, Y: Ordinate<T>>(data: &[(T,Y)]) -> Result<Vec<Y>, IntegrationError> {
+    validate::check_points(data, 2)?;
+
+    Ok(cumulative_trapezoidal(data))
+}
+
+/// Computes running integrals with `cumulative_simpson`, checking its
+/// input first.
+///
+/// Fails if there are fewer than 2 points, if any coordinate is not finite
+/// or if the abscissae are not strictly increasing.
+pub fn try_cumulative_simpson<T: Float, Y: Ordinate<T>>(data: &[(T,Y)]) -> Result<Vec<Y>, IntegrationError> {
+    validate::check_points(data, 2)?;
+
+    Ok(cumulative_simpson(data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use integrate::{simpson_nonuniform, trapezoidal_nonuniform};
+
+    #[test]
+    fn test_cumulative_trapezoidal_empty_data() {
+        let data: [(f64,f64); 0] = [];
+
+        let expected: Vec<f64> = vec![];
+        let actual = cumulative_trapezoidal(&data);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_cumulative_trapezoidal_one_element() {
+        let data = [(0.0, 1.0)];
+
+        let expected = vec![0.0];
+        let actual = cumulative_trapezoidal(&data);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_cumulative_trapezoidal_ends_at_total() {
+        // This data represents sin^2(x)
+        let data = [
+            (0.0,  0.0), 
+            (0.25, 0.0612087), 
+            (0.5,  0.229849), 
+            (0.75, 0.464631), 
+            (1.0,  0.708073)];
+
+        let expected = trapezoidal_nonuniform(&data);
+        let actual = cumulative_trapezoidal(&data);
+
+        assert_eq!(5, actual.len());
+        assert_eq!(expected, actual[4]);
+    }
+
+    #[test]
+    fn test_cumulative_simpson_quadratic_is_exact() {
+        // y = x^2 sampled irregularly, with an even number of points
+        let xs = [0.0, 0.3, 1.0, 1.2, 2.0, 2.7];
+        let data: Vec<(f64,f64)> = xs.iter().map(|&x| (x, x*x)).collect();
+
+        let actual = cumulative_simpson(&data);
+
+        for (x, value) in xs.iter().zip(&actual) {
+            assert!((x*x*x/3.0 - value).abs() < 1e-14, "x = {}", x);
+        }
+    }
+
+    #[test]
+    fn test_cumulative_simpson_matches_simpson() {
+        let data: Vec<(f64,f64)> = (0..9)
+            .map(|i| {
+                let x = 0.1*(i*i) as f64;
+                (x, x.cos())
+            })
+            .collect();
+
+        let expected = simpson_nonuniform(&data);
+        let actual = cumulative_simpson(&data);
+
+        assert!((expected - actual[8]).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_cumulative_simpson_two_elements() {
+        let data = [(0.0, 0.0), (1.0, 1.0)];
+
+        let expected = vec![0.0, 0.5];
+        let actual = cumulative_simpson(&data);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_cumulative_simpson_position_from_acceleration() {
+        // Integrating acceleration -sin(t) twice gives position sin(t)
+        let data: Vec<(f64,f64)> = (0..201)
+            .map(|i| {
+                let t = 0.01*i as f64;
+                (t, -t.sin())
+            })
+            .collect();
+
+        let velocity: Vec<(f64,f64)> = cumulative_simpson(&data)
+            .iter()
+            .zip(&data)
+            .map(|(v, p)| (p.0, v + 1.0))
+            .collect();
+        let position = cumulative_simpson(&velocity);
+
+        for (p, x) in velocity.iter().zip(&position) {
+            assert!((p.0.sin() - x).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_try_cumulative_simpson_two_elements() {
+        let data = [(0.0, 0.0), (1.0, 1.0)];
+
+        assert_eq!(Ok(vec![0.0, 0.5]), try_cumulative_simpson(&data));
+        assert_eq!(Err(IntegrationError::TooFewPoints { required: 2, actual: 1 }), try_cumulative_simpson(&data[..1]));
+    }
+
+    #[test]
+    fn test_try_cumulative_simpson_unsorted() {
+        let data = [(0.0, 0.0), (2.0, 1.0), (1.0, 1.0)];
+
+        let expected = Err(IntegrationError::Unsorted { index: 2 });
+        let actual = try_cumulative_simpson(&data);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_cumulative_simpson_f32_matches_f64() {
+        let data = [(0.0, 0.0), (0.5, 0.25), (1.5, 2.25), (2.0, 4.0), (3.0, 9.0)];
+        let narrow: Vec<(f32,f32)> = data.iter().map(|&(x, y)| (x as f32, y as f32)).collect();
+
+        let expected = cumulative_simpson(&data);
+        let actual = cumulative_simpson(&narrow);
+
+        for (e, a) in expected.iter().zip(&actual) {
+            assert!((e - *a as f64).abs() < 1e-5);
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use std::error::Error;
+use std::fmt;
+
+/// The reasons an integration rule can refuse its input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntegrationError {
+    /// The rule needs at least `required` points but only `actual` were given.
+    TooFewPoints { required: usize, actual: usize },
+
+    /// The rule needs an odd number of points but `actual` were given.
+    WrongParity { actual: usize },
+
+    /// The rule needs a multiple of `multiple` intervals but `actual` were
+    /// given.
+    WrongIntervalCount { multiple: usize, actual: usize },
+
+    /// The interval ending at point `index` is not as wide as the first one.
+    UnevenSpacing { index: usize },
+
+    /// The point at `index` has a NaN or infinite coordinate.
+    NonFinite { index: usize },
+
+    /// The abscissa at `index` is not greater than the one before it.
+    Unsorted { index: usize },
+
+    /// A bound of the interval of integration is NaN or infinite.
+    NonFiniteBounds,
+
+    /// A tolerance is negative or NaN, or no tolerance is positive.
+    InvalidTolerance,
+
+    /// The option called `name` has a value the rule cannot use.
+    InvalidParameter { name: &'static str },
+
+    /// The grid should hold `expected` values but holds `actual`.
+    ShapeMismatch { expected: usize, actual: usize },
+
+    /// Inputs that describe the same dimensions have `expected` and
+    /// `actual` entries.
+    DimensionMismatch { expected: usize, actual: usize },
+
+    /// The triangle at index `triangle` refers to a vertex that does not
+    /// exist.
+    MissingVertex { triangle: usize },
+
+    /// There are `expected` abscissae but `actual` ordinates.
+    LengthMismatch { expected: usize, actual: usize },
+
+    /// The integrand returned NaN or an infinite value inside the interval.
+    NonFiniteIntegrand,
+}
+
+impl fmt::Display for IntegrationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            IntegrationError::TooFewPoints { required, actual } =>
+                write!(f, "expected at least {} points, got {}", required, actual),
+            IntegrationError::WrongParity { actual } =>
+                write!(f, "expected an odd number of points, got {}", actual),
+            IntegrationError::WrongIntervalCount { multiple, actual } =>
+                write!(f, "expected a multiple of {} intervals, got {}", multiple, actual),
+            IntegrationError::UnevenSpacing { index } =>
+                write!(f, "interval ending at point {} is not evenly spaced", index),
+            IntegrationError::NonFinite { index } =>
+                write!(f, "point {} is not finite", index),
+            IntegrationError::Unsorted { index } =>
+                write!(f, "point {} is not in increasing order of x", index),
+            IntegrationError::NonFiniteBounds =>
+                write!(f, "bounds of integration are not finite"),
+            IntegrationError::InvalidTolerance =>
+                write!(f, "tolerance must be non-negative with at least one positive"),
+            IntegrationError::InvalidParameter { name } =>
+                write!(f, "invalid value for {}", name),
+            IntegrationError::ShapeMismatch { expected, actual } =>
+                write!(f, "expected {} grid values, got {}", expected, actual),
+            IntegrationError::DimensionMismatch { expected, actual } =>
+                write!(f, "expected {} dimensions, got {}", expected, actual),
+            IntegrationError::MissingVertex { triangle } =>
+                write!(f, "triangle {} refers to a missing vertex", triangle),
+            IntegrationError::LengthMismatch { expected, actual } =>
+                write!(f, "expected {} ordinates to match the abscissae, got {}", expected, actual),
+            IntegrationError::NonFiniteIntegrand =>
+                write!(f, "integrand is not finite inside the interval"),
+        }
+    }
+}
+
+impl Error for IntegrationError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_too_few_points() {
+        let error = IntegrationError::TooFewPoints { required: 3, actual: 1 };
+
+        let expected = "expected at least 3 points, got 1";
+        let actual = error.to_string();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_display_wrong_parity() {
+        let error = IntegrationError::WrongParity { actual: 4 };
+
+        let expected = "expected an odd number of points, got 4";
+        let actual = error.to_string();
+
+        assert_eq!(expected, actual);
+    }
+}
@@ -0,0 +1,150 @@
+//! Wynn's epsilon algorithm, used to accelerate the sequence of estimates
+//! produced while an adaptive rule refines towards a singularity.
+
+use std::ops::Div;
+
+use super::Ordinate;
+
+/// The most terms of the sequence kept for extrapolation.
+const MAX_TERMS: usize = 50;
+
+/// A value whose sequences can be extrapolated: a real or a complex
+/// number. The epsilon algorithm divides by differences of terms, so the
+/// values must form a field.
+pub trait Scalar: Ordinate<f64> + From<f64> + Div<Output = Self> {}
+
+impl<V: Ordinate<f64> + From<f64> + Div<Output = V>> Scalar for V {}
+
+/// A sequence of estimates and the extrapolated limits found so far.
+pub struct Extrapolation<V = f64> {
+    terms: Vec<V>,
+    limits: Vec<V>,
+}
+
+impl<V: Scalar> Extrapolation<V> {
+    pub fn new() -> Extrapolation<V> {
+        Extrapolation { terms: Vec::new(), limits: Vec::new() }
+    }
+
+    /// The number of terms of the sequence seen so far.
+    pub fn len(&self) -> usize {
+        self.terms.len()
+    }
+
+    /// Adds the next term of the sequence and returns the extrapolated
+    /// limit with an estimate of its error.
+    ///
+    /// The error is infinite until three limits have been found; after
+    /// that it is the spread of the last three, measured with `norm`.
+    pub fn push(&mut self, term: V) -> (V, f64) {
+        if self.terms.len() == MAX_TERMS {
+            self.terms.remove(0);
+        }
+        self.terms.push(term);
+
+        let limit = epsilon(&self.terms);
+        self.limits.push(limit);
+
+        let n = self.limits.len();
+        if n < 3 {
+            return (limit, f64::INFINITY);
+        }
+
+        let spread = (limit - self.limits[n - 2]).norm() + (limit - self.limits[n - 3]).norm();
+        (limit, spread.max(5.0*f64::EPSILON*limit.norm()))
+    }
+}
+
+/// Returns the best estimate of the limit of `terms` from the epsilon
+/// table: the newest entry of the highest even column.
+fn epsilon<V: Scalar>(terms: &[V]) -> V {
+    // previous holds column k - 1 and current column k; entry n of each
+    // column depends on terms n onwards, so the last entry of every column
+    // involves the newest term.
+    let mut previous = vec![V::zero(); terms.len() + 1];
+    let mut current = terms.to_vec();
+    let mut best = terms[terms.len() - 1];
+
+    let mut column = 0;
+    while current.len() > 1 {
+        let mut next = Vec::with_capacity(current.len() - 1);
+
+        for i in 0..current.len() - 1 {
+            let delta = current[i + 1] - current[i];
+            if delta == V::zero() {
+                // The sequence has already converged at this column.
+                return if column % 2 == 0 { current[i + 1] } else { best };
+            }
+            next.push(previous[i + 1] + V::from(1.0)/delta);
+        }
+
+        column += 1;
+        if column % 2 == 0 {
+            let newest = next[next.len() - 1];
+            if !newest.is_finite() {
+                break;
+            }
+            best = newest;
+        }
+
+        previous = current;
+        current = next;
+    }
+
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use integrate::Complex;
+
+    #[test]
+    fn test_epsilon_geometric_series() {
+        // Partial sums of a geometric series are extrapolated exactly
+        // after three terms.
+        let mut extrapolation = Extrapolation::new();
+        let mut sum = 0.0;
+        let mut limit = 0.0;
+        for k in 0..6 {
+            sum += 0.5f64.powi(k);
+            limit = extrapolation.push(sum).0;
+        }
+
+        assert!((limit - 2.0).abs() < 1e-14);
+        assert_eq!(6, extrapolation.len());
+    }
+
+    #[test]
+    fn test_epsilon_complex_geometric_series() {
+        let ratio = Complex::new(0.0, 0.5);
+        let mut extrapolation = Extrapolation::new();
+        let mut power = Complex::from(1.0);
+        let mut sum = Complex::from(0.0);
+        let mut limit = sum;
+        for _ in 0..6 {
+            sum += power;
+            power = power*ratio;
+            limit = extrapolation.push(sum).0;
+        }
+
+        let expected = Complex::from(1.0)/(Complex::from(1.0) - ratio);
+        assert!((limit - expected).norm() < 1e-14);
+    }
+
+    #[test]
+    fn test_epsilon_alternating_series() {
+        // ln 2 = 1 - 1/2 + 1/3 - ...
+        let mut extrapolation = Extrapolation::new();
+        let mut sum = 0.0;
+        let mut result = (0.0, 0.0);
+        for k in 1..16 {
+            let term = 1.0/k as f64;
+            sum += if k % 2 == 1 { term } else { -term };
+            result = extrapolation.push(sum);
+        }
+
+        assert!((result.0 - 2f64.ln()).abs() < 1e-10);
+        assert!(result.1 < 1e-8);
+    }
+}
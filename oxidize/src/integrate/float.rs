@@ -0,0 +1,106 @@
+//! The floating-point types the sample-based rules work with.
+
+use std::fmt::Debug;
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// A floating-point number the sample-based rules can integrate.
+///
+/// Implemented for `f32` and `f64`. Implement it for another type, such as
+/// a double-double or an arbitrary-precision float, to run the rules in
+/// that precision.
+pub trait Float: Copy + Debug + PartialOrd
+    + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self> + Neg<Output = Self>
+    + AddAssign + SubAssign + MulAssign
+{
+    /// Converts an `f64` constant, rounding it if the type is less
+    /// precise.
+    fn from_f64(x: f64) -> Self;
+
+    /// Converts a count, such as a number of points.
+    fn from_usize(n: usize) -> Self;
+
+    /// Returns the absolute value.
+    fn abs(self) -> Self;
+
+    /// Returns `sqrt(self^2 + other^2)` without undue overflow.
+    fn hypot(self, other: Self) -> Self;
+
+    /// Returns `true` unless the value is NaN or infinite.
+    fn is_finite(self) -> bool;
+
+    /// Returns the difference between 1 and the next larger value.
+    fn epsilon() -> Self;
+
+    /// Returns 0.
+    fn zero() -> Self {
+        Self::from_f64(0.0)
+    }
+}
+
+/// A value the rules can integrate over abscissae of type `T`.
+///
+/// Implemented for every `Float`, which integrates to itself, and for
+/// `Complex<T>`. The rules only add ordinates together and scale them by
+/// widths, and the adaptive rules measure their errors with `norm`, so any
+/// normed vector space over `T` can implement it.
+pub trait Ordinate<T: Float>: Copy + Debug + PartialEq
+    + Add<Output = Self> + Sub<Output = Self> + Mul<T, Output = Self> + Div<T, Output = Self>
+    + AddAssign + SubAssign
+{
+    /// Returns 0.
+    fn zero() -> Self;
+
+    /// Returns `true` unless any component is NaN or infinite.
+    fn is_finite(self) -> bool;
+
+    /// Returns the magnitude: the absolute value of a real number, or the
+    /// modulus of a complex one.
+    fn norm(self) -> T;
+}
+
+impl<T: Float> Ordinate<T> for T {
+    fn zero() -> T {
+        Float::zero()
+    }
+
+    fn is_finite(self) -> bool {
+        Float::is_finite(self)
+    }
+
+    fn norm(self) -> T {
+        Float::abs(self)
+    }
+}
+
+macro_rules! impl_float {
+    ($t:ident) => {
+        impl Float for $t {
+            fn from_f64(x: f64) -> $t {
+                x as $t
+            }
+
+            fn from_usize(n: usize) -> $t {
+                n as $t
+            }
+
+            fn abs(self) -> $t {
+                $t::abs(self)
+            }
+
+            fn hypot(self, other: $t) -> $t {
+                $t::hypot(self, other)
+            }
+
+            fn is_finite(self) -> bool {
+                $t::is_finite(self)
+            }
+
+            fn epsilon() -> $t {
+                $t::EPSILON
+            }
+        }
+    };
+}
+
+impl_float!(f32);
+impl_float!(f64);
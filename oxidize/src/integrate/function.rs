@@ -0,0 +1,194 @@
+//! Rules that sample a function over an interval instead of taking samples.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use sum::Summation;
+
+use super::{kernel, Float, IntegrationError, Ordinate};
+
+/// Computes the integral of `f` over `[a, b]` using the trapezoid rule on
+/// `n` evenly-spaced slices.
+///
+/// Fails if `n` is 0 or if either bound is not finite.
+///
+/// ```
+/// use oxidize::integrate::trapezoidal_fn;
+///
+/// let area = trapezoidal_fn(|x| 2.0*x, 0.0, 1.0, 4).unwrap();
+/// assert_eq!(1.0, area);
+/// ```
+pub fn trapezoidal_fn<T: Float, Y: Ordinate<T>, F: Fn(T) -> Y>(f: F, a: T, b: T, n: usize) -> Result<Y, IntegrationError> {
+    check_interval(a, b)?;
+    if n < 1 {
+        return Err(IntegrationError::TooFewPoints { required: 2, actual: n + 1 });
+    }
+
+    let h = (b - a)/T::from_usize(n);
+    Ok(kernel::trapezoid(Summation::Naive, n + 1, h, |i| f(abscissa(a, b, h, n, i))))
+}
+
+/// Computes the integral of `f` over `[a, b]` using Simpson's rule on `n`
+/// evenly-spaced slices.
+///
+/// Fails if `n` is 0 or odd, or if either bound is not finite.
+///
+/// ```
+/// use oxidize::integrate::simpson_fn;
+///
+/// let area = simpson_fn(|x| 3.0*x*x, 0.0, 1.0, 2).unwrap();
+/// assert_eq!(1.0, area);
+/// ```
+pub fn simpson_fn<T: Float, Y: Ordinate<T>, F: Fn(T) -> Y>(f: F, a: T, b: T, n: usize) -> Result<Y, IntegrationError> {
+    check_interval(a, b)?;
+    if n < 2 {
+        return Err(IntegrationError::TooFewPoints { required: 3, actual: n + 1 });
+    }
+    if !n.is_multiple_of(2) {
+        return Err(IntegrationError::WrongParity { actual: n + 1 });
+    }
+
+    let h = (b - a)/T::from_usize(n);
+    Ok(kernel::simpson(Summation::Naive, n + 1, h, |i| f(abscissa(a, b, h, n, i))))
+}
+
+/// Checks that both bounds of an interval are finite.
+pub fn check_interval<T: Float>(a: T, b: T) -> Result<(), IntegrationError> {
+    if !a.is_finite() || !b.is_finite() {
+        return Err(IntegrationError::NonFiniteBounds);
+    }
+
+    Ok(())
+}
+
+/// An integrand that counts its evaluations, from any number of threads.
+pub struct Counted<'a, F: 'a> {
+    f: &'a F,
+    evaluations: AtomicUsize,
+}
+
+impl<'a, F> Counted<'a, F> {
+    pub fn new(f: &'a F) -> Counted<'a, F> {
+        Counted { f, evaluations: AtomicUsize::new(0) }
+    }
+
+    pub fn call<Y>(&self, x: f64) -> Y
+        where F: Fn(f64) -> Y
+    {
+        self.evaluations.fetch_add(1, Ordering::Relaxed);
+        (self.f)(x)
+    }
+
+    pub fn evaluations(&self) -> usize {
+        self.evaluations.load(Ordering::Relaxed)
+    }
+}
+
+/// The points of a rule with their weights, as `(x, weight)`, or `None`
+/// for a point the rule skips.
+pub type Points<'p> = &'p (dyn Fn(usize) -> Option<(f64, f64)> + Sync);
+
+/// Returns the sum of `f(x)*weight` over the first `n` of `points`, added
+/// in index order.
+pub fn weighted_sum<Y: Ordinate<f64>, F: Fn(f64) -> Y>(f: &Counted<F>, n: usize, points: Points) -> Y {
+    let mut result = Y::zero();
+    for i in 0..n {
+        if let Some((x, weight)) = points(i) {
+            result += f.call(x)*weight;
+        }
+    }
+    result
+}
+
+/// Returns the `i`th of `n + 1` evenly-spaced points over `[a, b]`, hitting
+/// `b` exactly at the end.
+fn abscissa<T: Float>(a: T, b: T, h: T, n: usize, i: usize) -> T {
+    if i == n {
+        b
+    } else {
+        a + T::from_usize(i)*h
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use integrate::{simpson, trapezoidal};
+
+    #[test]
+    fn test_trapezoidal_fn_zero_slices() {
+        let expected = Err(IntegrationError::TooFewPoints { required: 2, actual: 1 });
+        let actual = trapezoidal_fn(|x| x, 0.0, 1.0, 0);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_trapezoidal_fn_infinite_bound() {
+        let expected = Err(IntegrationError::NonFiniteBounds);
+        let actual = trapezoidal_fn(|x| x, 0.0, f64::INFINITY, 4);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_trapezoidal_fn_matches_samples() {
+        let data: Vec<(f64,f64)> = (0..5)
+            .map(|i| {
+                let x = 0.25*i as f64;
+                (x, x.sin()*x.sin())
+            })
+            .collect();
+
+        let expected = Ok(trapezoidal(&data));
+        let actual = trapezoidal_fn(|x: f64| x.sin()*x.sin(), 0.0, 1.0, 4);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_trapezoidal_fn_reversed_bounds() {
+        let expected = Ok(-1.0);
+        let actual = trapezoidal_fn(|x| 2.0*x, 1.0, 0.0, 4);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_simpson_fn_odd_slices() {
+        let expected = Err(IntegrationError::WrongParity { actual: 4 });
+        let actual = simpson_fn(|x| x, 0.0, 1.0, 3);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_simpson_fn_matches_samples() {
+        let data: Vec<(f64,f64)> = (0..5)
+            .map(|i| {
+                let x = 0.25*i as f64;
+                (x, x.sin()*x.sin())
+            })
+            .collect();
+
+        let expected = Ok(simpson(&data));
+        let actual = simpson_fn(|x: f64| x.sin()*x.sin(), 0.0, 1.0, 4);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_simpson_fn_sin() {
+        let expected = 2.0;
+        let actual = simpson_fn(f64::sin, 0.0, std::f64::consts::PI, 64).unwrap();
+
+        assert!((expected - actual).abs() < 1e-7);
+    }
+
+    #[test]
+    fn test_simpson_fn_f32() {
+        let expected = 2.0f32;
+        let actual = simpson_fn(f32::sin, 0.0, std::f32::consts::PI, 64).unwrap();
+
+        assert!((expected - actual).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,534 @@
+//! Gaussian quadrature.
+
+use std::collections::BTreeMap;
+use std::f64::consts::PI;
+use std::sync::{Arc, Mutex};
+
+use super::function::check_interval;
+use super::{IntegrationError, Ordinate};
+
+/// The most Newton steps taken to polish a single node.
+const MAX_NEWTON_STEPS: usize = 100;
+
+/// The largest order of rule that is kept in a cache, which bounds each
+/// cache to this many rules.
+const MAX_CACHED_ORDER: usize = 64;
+
+/// The nodes and weights of an `n`-point Gaussian quadrature rule, with
+/// the nodes in increasing order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GaussRule {
+    nodes: Vec<f64>,
+    weights: Vec<f64>,
+}
+
+impl GaussRule {
+    /// The number of nodes in the rule.
+    pub fn order(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// The nodes of the rule, in increasing order.
+    pub fn nodes(&self) -> &[f64] {
+        &self.nodes
+    }
+
+    /// The weight of each node.
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    /// Returns the weighted sum of `f` over the nodes of the rule.
+    ///
+    /// For a rule from `laguerre_rule` or `hermite_rule` this is the
+    /// integral of `f` against the rule's weight function. `f` may return
+    /// any `Ordinate`, such as a `Complex<f64>`.
+    pub fn sum<Y: Ordinate<f64>, F: Fn(f64) -> Y>(&self, f: F) -> Y {
+        let mut result = Y::zero();
+
+        for (node, &weight) in self.nodes.iter().zip(&self.weights) {
+            result += f(*node)*weight;
+        }
+
+        result
+    }
+
+    /// Applies a Gauss–Legendre rule to `f` over `[a, b]`.
+    ///
+    /// Assumptions: 
+    /// 1. That the rule comes from `legendre_rule`, so that its nodes lie
+    ///    in `[-1, 1]`.
+    pub fn integrate<Y: Ordinate<f64>, F: Fn(f64) -> Y>(&self, f: F, a: f64, b: f64) -> Y {
+        let half = 0.5*(b - a);
+        let mid = 0.5*(a + b);
+        let mut result = Y::zero();
+
+        for (node, &weight) in self.nodes.iter().zip(&self.weights) {
+            result += f(mid + half*node)*weight;
+        }
+
+        result*half
+    }
+}
+
+/// Rules of up to `MAX_CACHED_ORDER` points built so far, keyed by order.
+struct RuleCache(Mutex<BTreeMap<usize, Arc<GaussRule>>>);
+
+impl RuleCache {
+    const fn new() -> RuleCache {
+        RuleCache(Mutex::new(BTreeMap::new()))
+    }
+
+    fn get(&self, n: usize, build: fn(usize) -> GaussRule) -> Arc<GaussRule> {
+        if n > MAX_CACHED_ORDER {
+            return Arc::new(build(n));
+        }
+
+        // A panic while building a rule cannot leave a half-inserted entry,
+        // so a poisoned cache is still consistent.
+        let mut rules = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        rules.entry(n).or_insert_with(|| Arc::new(build(n))).clone()
+    }
+}
+
+static LEGENDRE: RuleCache = RuleCache::new();
+static LAGUERRE: RuleCache = RuleCache::new();
+static HERMITE: RuleCache = RuleCache::new();
+
+/// Returns the `n`-point Gauss–Legendre rule on `[-1, 1]`.
+///
+/// The nodes are the roots of the Legendre polynomial `P_n`, found by
+/// Newton iteration. Each rule of up to 64 points is computed once and
+/// then shared from a cache; larger rules are computed on every call.
+///
+/// Fails if `n` is 0.
+pub fn legendre_rule(n: usize) -> Result<Arc<GaussRule>, IntegrationError> {
+    if n < 1 {
+        return Err(IntegrationError::InvalidParameter { name: "n" });
+    }
+
+    Ok(LEGENDRE.get(n, build_legendre))
+}
+
+fn build_legendre(n: usize) -> GaussRule {
+    let mut nodes = vec![0.0; n];
+    let mut weights = vec![0.0; n];
+
+    // The roots are symmetric about 0, so only the positive half is found.
+    for i in 0..n.div_ceil(2) {
+        let mut z = (PI*(i as f64 + 0.75)/(n as f64 + 0.5)).cos();
+        let mut derivative = 0.0;
+
+        for _ in 0..MAX_NEWTON_STEPS {
+            let (p, dp) = legendre(n, z);
+            derivative = dp;
+
+            let step = p/dp;
+            z -= step;
+            if step.abs() <= 1e-16 {
+                break;
+            }
+        }
+
+        let weight = 2.0/((1.0 - z*z)*derivative*derivative);
+        nodes[i] = -z;
+        nodes[n - 1 - i] = z;
+        weights[i] = weight;
+        weights[n - 1 - i] = weight;
+    }
+
+    GaussRule { nodes, weights }
+}
+
+/// Returns the `n`-point Gauss–Laguerre rule, for integrals over
+/// `[0, inf)` against the weight `e^(-x)`.
+///
+/// The nodes are the roots of the Laguerre polynomial `L_n`, found by
+/// Newton iteration, and the rule is cached like `legendre_rule`.
+///
+/// Fails if `n` is 0.
+pub fn laguerre_rule(n: usize) -> Result<Arc<GaussRule>, IntegrationError> {
+    if n < 1 {
+        return Err(IntegrationError::InvalidParameter { name: "n" });
+    }
+
+    Ok(LAGUERRE.get(n, build_laguerre))
+}
+
+/// Returns the `n`-point Gauss–Hermite rule, for integrals over the whole
+/// real line against the weight `e^(-x^2)`.
+///
+/// The nodes are the roots of the Hermite polynomial `H_n`, found by Newton
+/// iteration, and the rule is cached like `legendre_rule`.
+///
+/// Fails if `n` is 0.
+pub fn hermite_rule(n: usize) -> Result<Arc<GaussRule>, IntegrationError> {
+    if n < 1 {
+        return Err(IntegrationError::InvalidParameter { name: "n" });
+    }
+
+    Ok(HERMITE.get(n, build_hermite))
+}
+
+fn build_laguerre(n: usize) -> GaussRule {
+    let mut nodes: Vec<f64> = Vec::with_capacity(n);
+    let mut weights = Vec::with_capacity(n);
+    let nf = n as f64;
+
+    // Initial guesses follow Numerical Recipes' gaulag, each root starting
+    // from an extrapolation of the ones before it.
+    let mut z = 0.0;
+    for i in 0..n {
+        z = match i {
+            0 => 3.0/(1.0 + 2.4*nf),
+            1 => z + 15.0/(1.0 + 2.5*nf),
+            _ => {
+                let ai = (i - 1) as f64;
+                z + (1.0 + 2.55*ai)/(1.9*ai)*(z - nodes[i - 2])
+            }
+        };
+
+        let mut derivative = 0.0;
+        let mut previous = 0.0;
+        for _ in 0..MAX_NEWTON_STEPS {
+            let (p, p_prev) = laguerre(n, z);
+            derivative = (nf*p - nf*p_prev)/z;
+            previous = p_prev;
+
+            let step = p/derivative;
+            z -= step;
+            if step.abs() <= 1e-15*z.abs().max(1.0) {
+                break;
+            }
+        }
+
+        nodes.push(z);
+        weights.push(-1.0/(derivative*nf*previous));
+    }
+
+    GaussRule { nodes, weights }
+}
+
+/// Evaluates the Laguerre polynomials `L_n` and `L_(n-1)` at `z`.
+fn laguerre(n: usize, z: f64) -> (f64, f64) {
+    let mut p1 = 1.0;
+    let mut p2 = 0.0;
+
+    for j in 1..n + 1 {
+        let j = j as f64;
+        let p3 = p2;
+        p2 = p1;
+        p1 = ((2.0*j - 1.0 - z)*p2 - (j - 1.0)*p3)/j;
+    }
+
+    (p1, p2)
+}
+
+fn build_hermite(n: usize) -> GaussRule {
+    let mut nodes = vec![0.0; n];
+    let mut weights = vec![0.0; n];
+    let nf = n as f64;
+
+    // Initial guesses follow Numerical Recipes' gauher, working inwards
+    // from the largest root. Only the non-negative roots are found.
+    let mut z = 0.0;
+    for i in 0..n.div_ceil(2) {
+        let top = n - 1;
+        z = match i {
+            0 => (2.0*nf + 1.0).sqrt() - 1.85575*(2.0*nf + 1.0).powf(-0.16667),
+            1 => z - 1.14*nf.powf(0.426)/z,
+            2 => 1.86*z - 0.86*nodes[top],
+            3 => 1.91*z - 0.91*nodes[top - 1],
+            _ => 2.0*z - nodes[top - i + 2],
+        };
+
+        let mut derivative = 0.0;
+        for _ in 0..MAX_NEWTON_STEPS {
+            let (p, p_prev) = hermite(n, z);
+            derivative = (2.0*nf).sqrt()*p_prev;
+
+            let step = p/derivative;
+            z -= step;
+            if step.abs() <= 1e-15*z.abs().max(1.0) {
+                break;
+            }
+        }
+
+        let weight = 2.0/(derivative*derivative);
+        nodes[i] = -z;
+        nodes[n - 1 - i] = z;
+        weights[i] = weight;
+        weights[n - 1 - i] = weight;
+    }
+
+    GaussRule { nodes, weights }
+}
+
+/// Evaluates the orthonormal Hermite functions of order `n` and `n - 1` at
+/// `z`, scaled so that their squares integrate to 1 against `e^(-x^2)`.
+fn hermite(n: usize, z: f64) -> (f64, f64) {
+    let mut p1 = PI.powf(-0.25);
+    let mut p2 = 0.0;
+
+    for j in 0..n {
+        let j = j as f64;
+        let p3 = p2;
+        p2 = p1;
+        p1 = z*(2.0/(j + 1.0)).sqrt()*p2 - (j/(j + 1.0)).sqrt()*p3;
+    }
+
+    (p1, p2)
+}
+
+/// Evaluates the Legendre polynomial `P_n` and its derivative at `z`, for
+/// `n >= 1` and `|z| < 1`.
+fn legendre(n: usize, z: f64) -> (f64, f64) {
+    let mut p0 = 1.0;
+    let mut p1 = z;
+
+    for k in 2..n + 1 {
+        let k = k as f64;
+        let p2 = ((2.0*k - 1.0)*z*p1 - (k - 1.0)*p0)/k;
+        p0 = p1;
+        p1 = p2;
+    }
+
+    let dp = n as f64*(z*p1 - p0)/(z*z - 1.0);
+    (p1, dp)
+}
+
+/// Computes the integral of `f` over `[a, b]` using `n`-point
+/// Gauss–Legendre quadrature.
+///
+/// The result is exact for polynomials of degree up to `2n - 1`. `f` may
+/// return any `Ordinate`, such as a `Complex<f64>`.
+///
+/// Fails if `n` is 0 or if either bound is not finite.
+///
+/// ```
+/// use oxidize::integrate::gauss_legendre;
+///
+/// let area = gauss_legendre(|x| x.powi(5), 0.0, 1.0, 3).unwrap();
+/// assert!((area - 1.0/6.0).abs() < 1e-15);
+/// ```
+pub fn gauss_legendre<Y: Ordinate<f64>, F: Fn(f64) -> Y>(f: F, a: f64, b: f64, n: usize) -> Result<Y, IntegrationError> {
+    check_interval(a, b)?;
+    let rule = legendre_rule(n)?;
+
+    Ok(rule.integrate(f, a, b))
+}
+
+/// Computes the integral of `f` over `[a, b]` by splitting it into
+/// `panels` equal subintervals and applying `n`-point Gauss–Legendre
+/// quadrature to each.
+///
+/// Fails if `n` or `panels` is 0 or if either bound is not finite.
+pub fn gauss_legendre_composite<Y: Ordinate<f64>, F: Fn(f64) -> Y>(f: F, a: f64, b: f64, n: usize, panels: usize) -> Result<Y, IntegrationError> {
+    check_interval(a, b)?;
+    let rule = legendre_rule(n)?;
+    if panels < 1 {
+        return Err(IntegrationError::InvalidParameter { name: "panels" });
+    }
+
+    let h = (b - a)/panels as f64;
+    let mut result = Y::zero();
+
+    for i in 0..panels {
+        let lower = a + i as f64*h;
+        let upper = if i + 1 == panels { b } else { lower + h };
+        result += rule.integrate(&f, lower, upper);
+    }
+
+    Ok(result)
+}
+
+/// Computes the integral of `e^(-x) f(x)` over `[0, inf)` using `n`-point
+/// Gauss–Laguerre quadrature.
+///
+/// The result is exact when `f` is a polynomial of degree up to `2n - 1`.
+///
+/// Fails if `n` is 0.
+///
+/// ```
+/// use oxidize::integrate::gauss_laguerre;
+///
+/// // The integral of x^3 e^(-x) is 3! = 6
+/// let area = gauss_laguerre(|x| x.powi(3), 2).unwrap();
+/// assert!((area - 6.0).abs() < 1e-13);
+/// ```
+pub fn gauss_laguerre<Y: Ordinate<f64>, F: Fn(f64) -> Y>(f: F, n: usize) -> Result<Y, IntegrationError> {
+    let rule = laguerre_rule(n)?;
+
+    Ok(rule.sum(f))
+}
+
+/// Computes the integral of `e^(-x^2) f(x)` over the whole real line using
+/// `n`-point Gauss–Hermite quadrature.
+///
+/// The result is exact when `f` is a polynomial of degree up to `2n - 1`.
+///
+/// Fails if `n` is 0.
+pub fn gauss_hermite<Y: Ordinate<f64>, F: Fn(f64) -> Y>(f: F, n: usize) -> Result<Y, IntegrationError> {
+    let rule = hermite_rule(n)?;
+
+    Ok(rule.sum(f))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_legendre_rule_two_points() {
+        let rule = legendre_rule(2).unwrap();
+        let node = 1.0/3f64.sqrt();
+
+        assert!((rule.nodes()[0] + node).abs() < 1e-15);
+        assert!((rule.nodes()[1] - node).abs() < 1e-15);
+        assert!((rule.weights()[0] - 1.0).abs() < 1e-15);
+        assert!((rule.weights()[1] - 1.0).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_legendre_rule_odd_order_has_zero_node() {
+        let rule = legendre_rule(5).unwrap();
+
+        assert_eq!(5, rule.order());
+        assert!(rule.nodes()[2].abs() < 1e-16);
+        assert!((rule.weights()[2] - 128.0/225.0).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_legendre_rule_weights_sum_to_two() {
+        for &n in &[1, 7, 20, 64, 200] {
+            let rule = legendre_rule(n).unwrap();
+            let sum: f64 = rule.weights().iter().sum();
+
+            assert!((sum - 2.0).abs() < 1e-13, "n = {}", n);
+        }
+    }
+
+    #[test]
+    fn test_legendre_rule_is_cached() {
+        let first = legendre_rule(12).unwrap();
+        let second = legendre_rule(12).unwrap();
+
+        assert!(Arc::ptr_eq(&first, &second));
+    }
+
+    #[test]
+    fn test_large_rules_are_not_cached() {
+        let first = legendre_rule(MAX_CACHED_ORDER + 1).unwrap();
+        let second = legendre_rule(MAX_CACHED_ORDER + 1).unwrap();
+
+        assert!(!Arc::ptr_eq(&first, &second));
+        assert_eq!(first, second);
+        assert!(LEGENDRE.0.lock().unwrap().keys().all(|&n| n <= MAX_CACHED_ORDER));
+    }
+
+    #[test]
+    fn test_legendre_rule_zero_order() {
+        let expected = Err(IntegrationError::InvalidParameter { name: "n" });
+        let actual = legendre_rule(0);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_gauss_legendre_polynomial_is_exact() {
+        // Degree 9 needs 5 points
+        let expected = 0.1;
+        let actual = gauss_legendre(|x| x.powi(9), 0.0, 1.0, 5).unwrap();
+
+        assert!((expected - actual).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_gauss_legendre_exp() {
+        let expected = 1f64.exp() - (-1f64).exp();
+        let actual = gauss_legendre(f64::exp, -1.0, 1.0, 10).unwrap();
+
+        assert!((expected - actual).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_gauss_legendre_composite_oscillatory() {
+        let expected = (1.0 - (40f64).cos())/40.0;
+        let actual = gauss_legendre_composite(|x| (40.0*x).sin(), 0.0, 1.0, 8, 16).unwrap();
+
+        assert!((expected - actual).abs() < 1e-13);
+    }
+
+    #[test]
+    fn test_gauss_legendre_composite_zero_panels() {
+        let expected = Err(IntegrationError::InvalidParameter { name: "panels" });
+        let actual = gauss_legendre_composite(f64::sin, 0.0, 1.0, 4, 0);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_laguerre_rule_weights_sum_to_one() {
+        for &n in &[1, 2, 5, 20, 60] {
+            let rule = laguerre_rule(n).unwrap();
+            let sum: f64 = rule.weights().iter().sum();
+
+            assert!((sum - 1.0).abs() < 1e-12, "n = {}", n);
+            assert!(rule.nodes().windows(2).all(|w| w[0] < w[1]));
+        }
+    }
+
+    #[test]
+    fn test_laguerre_rule_two_points() {
+        // The roots of L_2 are 2 -+ sqrt(2)
+        let rule = laguerre_rule(2).unwrap();
+
+        assert!((rule.nodes()[0] - (2.0 - 2f64.sqrt())).abs() < 1e-15);
+        assert!((rule.nodes()[1] - (2.0 + 2f64.sqrt())).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_gauss_laguerre_factorials() {
+        // The integral of x^k e^(-x) over [0, inf) is k!
+        let mut factorial = 1.0;
+        for k in 0..10 {
+            if k > 0 {
+                factorial *= k as f64;
+            }
+            let actual = gauss_laguerre(|x| x.powi(k), 5).unwrap();
+
+            assert!(((factorial - actual)/factorial).abs() < 1e-12, "k = {}", k);
+        }
+    }
+
+    #[test]
+    fn test_hermite_rule_weights_sum_to_sqrt_pi() {
+        for &n in &[1, 2, 5, 20, 61] {
+            let rule = hermite_rule(n).unwrap();
+            let sum: f64 = rule.weights().iter().sum();
+
+            assert!((sum - PI.sqrt()).abs() < 1e-12, "n = {}", n);
+            assert!(rule.nodes().windows(2).all(|w| w[0] < w[1]));
+        }
+    }
+
+    #[test]
+    fn test_gauss_hermite_moments() {
+        // The integral of x^2 e^(-x^2) is sqrt(pi)/2, of x^4 e^(-x^2) is 3 sqrt(pi)/4
+        let second = gauss_hermite(|x| x*x, 3).unwrap();
+        let fourth = gauss_hermite(|x| x.powi(4), 3).unwrap();
+        let odd = gauss_hermite(|x| x.powi(5), 3).unwrap();
+
+        assert!((second - 0.5*PI.sqrt()).abs() < 1e-14);
+        assert!((fourth - 0.75*PI.sqrt()).abs() < 1e-14);
+        assert!(odd.abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_gauss_hermite_zero_order() {
+        let expected = Err(IntegrationError::InvalidParameter { name: "n" });
+        let actual = gauss_hermite(|x| x, 0);
+
+        assert_eq!(expected, actual);
+    }
+}
@@ -0,0 +1,86 @@
+//! Weighted sums shared by the sample-based and function-based rules.
+//!
+//! Each kernel takes the number of points `n`, the spacing `h` and a way to
+//! look up the ordinate of point `i`, so the same arithmetic runs whether
+//! the ordinates come from a slice or from calling a function. The long
+//! runs of ordinates are added with the given `Summation`; with
+//! `Summation::Naive` they are added in index order.
+
+use super::{Float, Ordinate};
+use sum::Summation;
+
+/// The trapezoid rule over `n` evenly-spaced ordinates.
+///
+/// Assumptions: 
+/// 1. That there are 2 or more points.
+pub fn trapezoid<T: Float, V: Ordinate<T>, Y: Fn(usize) -> V>(summation: Summation, n: usize, h: T, y: Y) -> V {
+    let last = n - 1;
+    let mut result = summation.sum((y(0) + y(last))*T::from_f64(0.5), last - 1, |k| y(k + 1));
+
+    result = result*h;
+    result
+}
+
+/// Simpson's rule over `n` evenly-spaced ordinates.
+///
+/// Assumptions: 
+/// 1. That there are an odd number of points.
+/// 2. That there are 3 or more points.
+pub fn simpson<T: Float, V: Ordinate<T>, Y: Fn(usize) -> V>(summation: Summation, n: usize, h: T, y: Y) -> V {
+    let last = n - 1;
+    let mut result = V::zero();
+
+    result += y(0);
+    result += y(last);
+
+    let subres4 = summation.sum(V::zero(), last/2, |k| y(2*k + 1));
+    result += subres4*T::from_f64(4.0);
+
+    let subres2 = summation.sum(V::zero(), (last - 1)/2, |k| y(2*k + 2));
+    result += subres2*T::from_f64(2.0);
+
+    result = result*(h/T::from_f64(3.0));
+    result
+}
+
+/// Simpson's 3/8 rule over `n` evenly-spaced ordinates.
+///
+/// Assumptions: 
+/// 1. That the number of slices is a multiple of 3.
+/// 2. That there are 4 or more points.
+pub fn simpson38<T: Float, V: Ordinate<T>, Y: Fn(usize) -> V>(summation: Summation, n: usize, h: T, y: Y) -> V {
+    let last = n - 1;
+    let mut result = V::zero();
+
+    result += y(0);
+    result += y(last);
+
+    // The points not on a multiple of 3 come in pairs, 3k + 1 and 3k + 2.
+    let subres3 = summation.sum(V::zero(), 2*last/3, |k| y(3*(k/2) + k%2 + 1));
+    let subres2 = summation.sum(V::zero(), (last - 1)/3, |k| y(3*k + 3));
+    result += subres3*T::from_f64(3.0);
+    result += subres2*T::from_f64(2.0);
+
+    result = result*(T::from_f64(3.0)*h/T::from_f64(8.0));
+    result
+}
+
+/// Simpson's rules over any `n` evenly-spaced ordinates: the 1/3 rule
+/// throughout when `n` is odd, otherwise the 1/3 rule up to the last three
+/// slices and the 3/8 rule over those.
+///
+/// Assumptions: 
+/// 1. That there are 3 or more points.
+pub fn simpson_auto<T: Float, V: Ordinate<T>, Y: Fn(usize) -> V>(summation: Summation, n: usize, h: T, y: Y) -> V {
+    if !n.is_multiple_of(2) {
+        return simpson(summation, n, h, y);
+    }
+
+    let split = n - 4;
+    let tail = simpson38(summation, 4, h, |i| y(split + i));
+    if split == 0 {
+        return tail;
+    }
+
+    simpson(summation, split + 1, h, &y) + tail
+}
@@ -0,0 +1,244 @@
+//! Gauss–Kronrod rule pairs.
+//!
+//! Nodes and weights are those of QUADPACK's `dqk15` and `dqk21`, with all
+//! of their published digits. Only the non-negative nodes are stored, in
+//! decreasing order, ending with 0. The Gauss nodes are every other Kronrod
+//! node, starting from the second.
+
+#![allow(clippy::excessive_precision)]
+
+use super::Ordinate;
+
+/// The most points any rule evaluates the integrand at.
+pub const MAX_POINTS: usize = 21;
+
+/// A Gauss–Kronrod pair used to estimate an integral and its error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KronrodRule {
+    /// The 7-point Gauss rule extended to 15 points.
+    Gk15,
+
+    /// The 10-point Gauss rule extended to 21 points.
+    Gk21,
+}
+
+impl KronrodRule {
+    /// The number of points at which the integrand is evaluated.
+    pub fn points(&self) -> usize {
+        2*self.kronrod_nodes().len() - 1
+    }
+
+    fn kronrod_nodes(&self) -> &'static [f64] {
+        match *self {
+            KronrodRule::Gk15 => &XGK15,
+            KronrodRule::Gk21 => &XGK21,
+        }
+    }
+
+    fn kronrod_weights(&self) -> &'static [f64] {
+        match *self {
+            KronrodRule::Gk15 => &WGK15,
+            KronrodRule::Gk21 => &WGK21,
+        }
+    }
+
+    fn gauss_weights(&self) -> &'static [f64] {
+        match *self {
+            KronrodRule::Gk15 => &WG7,
+            KronrodRule::Gk21 => &WG10,
+        }
+    }
+}
+
+/// The estimate of a Gauss–Kronrod pair over one interval.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Estimate<V = f64> {
+    /// The Kronrod estimate of the integral.
+    pub area: V,
+
+    /// The error estimate, from the difference between the Gauss and
+    /// Kronrod estimates scaled as in QUADPACK.
+    pub error: f64,
+}
+
+/// Applies `rule` to `f` over `[a, b]`.
+pub fn estimate<V: Ordinate<f64>, F: Fn(f64) -> V>(rule: KronrodRule, f: &F, a: f64, b: f64) -> Estimate<V> {
+    let mut values = [V::zero(); MAX_POINTS];
+    let points = abscissae(rule, a, b);
+    for (value, &x) in values.iter_mut().zip(&points[..rule.points()]) {
+        *value = f(x);
+    }
+
+    combine(rule, a, b, &values[..rule.points()])
+}
+
+/// Returns the points at which `rule` evaluates the integrand over
+/// `[a, b]`: the midpoint first, then pairs on either side of it, working
+/// in from the ends. Only the first `rule.points()` are used.
+pub fn abscissae(rule: KronrodRule, a: f64, b: f64) -> [f64; MAX_POINTS] {
+    let xgk = rule.kronrod_nodes();
+    let center = xgk.len() - 1;
+
+    let half = 0.5*(b - a);
+    let mid = 0.5*(a + b);
+
+    let mut points = [mid; MAX_POINTS];
+    for j in 0..center {
+        let dx = half*xgk[j];
+        points[2*j + 1] = mid - dx;
+        points[2*j + 2] = mid + dx;
+    }
+    points
+}
+
+/// Applies `rule` over `[a, b]` given the integrand at the points returned
+/// by `abscissae`, in the same order. The error is measured with the
+/// norm of the values.
+pub fn combine<V: Ordinate<f64>>(rule: KronrodRule, a: f64, b: f64, values: &[V]) -> Estimate<V> {
+    let xgk = rule.kronrod_nodes();
+    let wgk = rule.kronrod_weights();
+    let wg = rule.gauss_weights();
+    let center = xgk.len() - 1;
+
+    let half = 0.5*(b - a);
+    let fc = values[0];
+
+    // The center is a Gauss node only when the Gauss rule has odd order.
+    let mut resg = if wg.len() > center/2 { fc*wg[center/2] } else { V::zero() };
+    let mut resk = fc*wgk[center];
+    let mut resabs = resk.norm();
+
+    for j in 0..center {
+        let f1 = values[2*j + 1];
+        let f2 = values[2*j + 2];
+
+        resk += (f1 + f2)*wgk[j];
+        resabs += wgk[j]*(f1.norm() + f2.norm());
+        if j % 2 == 1 {
+            resg += (f1 + f2)*wg[j/2];
+        }
+    }
+
+    let reskh = resk*0.5;
+    let mut resasc = wgk[center]*(fc - reskh).norm();
+    for j in 0..center {
+        resasc += wgk[j]*((values[2*j + 1] - reskh).norm() + (values[2*j + 2] - reskh).norm());
+    }
+
+    let area = resk*half;
+    resabs *= half.abs();
+    resasc *= half.abs();
+    let mut error = ((resk - resg)*half).norm();
+
+    if resasc != 0.0 && error != 0.0 {
+        error = resasc*(200.0*error/resasc).powf(1.5).min(1.0);
+    }
+    if resabs > f64::MIN_POSITIVE/(50.0*f64::EPSILON) {
+        error = error.max(50.0*f64::EPSILON*resabs);
+    }
+
+    Estimate { area, error }
+}
+
+const XGK15: [f64; 8] = [
+    0.991455371120812639206854697526329,
+    0.949107912342758524526189684047851,
+    0.864864423359769072789712788640926,
+    0.741531185599394439863864773280788,
+    0.586087235467691130294144845693013,
+    0.405845151377397166906606412076961,
+    0.207784955007898467600689403773245,
+    0.000000000000000000000000000000000,
+];
+
+const WGK15: [f64; 8] = [
+    0.022935322010529224963732008058970,
+    0.063092092629978553290700663189204,
+    0.104790010322250183839876322541518,
+    0.140653259715525918745189590510238,
+    0.169004726639267902826583426598550,
+    0.190350578064785409913256402421014,
+    0.204432940075298892414161999234649,
+    0.209482141084727828012999174891714,
+];
+
+const WG7: [f64; 4] = [
+    0.129484966168869693270611432679082,
+    0.279705391489276667901467771423780,
+    0.381830050505118944950369775488975,
+    0.417959183673469387755102040816327,
+];
+
+const XGK21: [f64; 11] = [
+    0.995657163025808080735527280689003,
+    0.973906528517171720077964012084452,
+    0.930157491355708226001207180059508,
+    0.865063366688984510732096688423493,
+    0.780817726586416897063717578345042,
+    0.679409568299024406234327365114874,
+    0.562757134668604683339000099272694,
+    0.433395394129247190799265943165784,
+    0.294392862701460198131126603103866,
+    0.148874338981631210884826001129720,
+    0.000000000000000000000000000000000,
+];
+
+const WGK21: [f64; 11] = [
+    0.011694638867371874278064396062192,
+    0.032558162307964727478818972459390,
+    0.054755896574351996031381300244580,
+    0.075039674810919952767043140916190,
+    0.093125454583697605535065465083366,
+    0.109387158802297641899210590325805,
+    0.123491976262065851077208067828712,
+    0.134709217311473325928054001771707,
+    0.142775938577060080797094273138717,
+    0.147739104901338491374841515972068,
+    0.149445554002916905664936468389821,
+];
+
+const WG10: [f64; 5] = [
+    0.066671344308688137593568809893332,
+    0.149451349150580593145776339657697,
+    0.219086362515982043995534934228163,
+    0.269266719309996355091226921569469,
+    0.295524224714752870173892994651338,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_polynomials(rule: KronrodRule, gauss_degree: i32, kronrod_degree: i32) {
+        for degree in 0..kronrod_degree + 1 {
+            let f = |x: f64| x.powi(degree);
+            let exact = 1.0/(degree as f64 + 1.0);
+
+            let result = estimate(rule, &f, 0.0, 1.0);
+            assert!((result.area - exact).abs() < 1e-15, "degree {}", degree);
+
+            // Without the Kronrod points, the Gauss rule alone is exact
+            // only up to its own degree, so the error estimate must vanish
+            // up to there.
+            if degree <= gauss_degree {
+                assert!(result.error < 1e-13, "degree {}", degree);
+            }
+        }
+    }
+
+    #[test]
+    fn test_gk15_polynomials() {
+        check_polynomials(KronrodRule::Gk15, 13, 22);
+    }
+
+    #[test]
+    fn test_gk21_polynomials() {
+        check_polynomials(KronrodRule::Gk21, 19, 31);
+    }
+
+    #[test]
+    fn test_points() {
+        assert_eq!(15, KronrodRule::Gk15.points());
+        assert_eq!(21, KronrodRule::Gk21.points());
+    }
+}
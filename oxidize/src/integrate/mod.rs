@@ -0,0 +1,805 @@
+//! Numerical integration of sampled data and of functions.
+//!
+//! Each sample-based rule takes a slice of `(x, y)` pairs, ordered by `x`,
+//! and returns an approximation of the integral of `y` over
+//! `[x_first, x_last]`. The `_fn` rules sample a function over an interval
+//! themselves and then apply the same arithmetic. Data kept in separate
+//! columns is integrated in place by the `_xy`, `_dx` and `_strided` rules
+//! and their `try_` twins.
+//!
+//! ```
+//! use oxidize::integrate;
+//!
+//! let data = [(0.0, 0.0), (0.5, 0.5), (1.0, 1.0)];
+//! assert_eq!(0.5, integrate::trapezoidal(&data));
+//! assert_eq!(0.5, integrate::simpson(&data));
+//! ```
+//!
+//! The plain rules return `0.0` when they cannot be applied. Every rule also
+//! has a `try_` variant that checks its assumptions first and reports any
+//! violation as an [`IntegrationError`](enum.IntegrationError.html):
+//!
+//! ```
+//! use oxidize::integrate::{self, IntegrationError};
+//!
+//! let data = [(0.0, 0.0), (0.5, 0.5), (1.0, 1.0), (1.5, 1.5)];
+//! assert_eq!(Err(IntegrationError::WrongParity { actual: 4 }), integrate::try_simpson(&data));
+//! ```
+//!
+//! The sample-based rules, their running-integral and gridded forms, and
+//! `trapezoidal_fn` and `simpson_fn` work in any type implementing
+//! [`Float`](trait.Float.html), such as `f32`. Their ordinates may also be
+//! [`Complex`](struct.Complex.html). The adaptive and Gaussian rules work
+//! in `f64`, but `adaptive_simpson`, `romberg`, `tanh_sinh` and the Gauss
+//! rules integrate functions returning any `Ordinate`, complex ones
+//! included, measuring errors with its `norm`; `quad_complex` and `contour`
+//! do the same for `quad_with`, whose own integrands are real.
+//!
+//! With the `parallel` feature, `par_trapezoidal` and `par_simpson`, with
+//! their `_xy` and `_dx` forms, apply their rule to runs of the samples on
+//! several threads. `par_quad_with`, `par_quad_complex`,
+//! `par_adaptive_simpson`, `par_romberg` and `par_tanh_sinh` evaluate the
+//! integrand on several threads. Their results do not depend on the number
+//! of threads.
+//!
+//! Samples that arrive one at a time, or that are too many to keep, are
+//! integrated by a [`StreamingIntegrator`](struct.StreamingIntegrator.html)
+//! as they come. `trapezoidal_iter` and `simpson_iter`, and their `_with`
+//! forms, integrate samples from any iterator the same way, without
+//! collecting them first.
+//!
+//! `simd_trapezoidal_dx` and `simd_simpson_dx` add up evenly-spaced `f64`
+//! ordinates with vector instructions, chosen at run time.
+//!
+//! ```
+//! use oxidize::integrate;
+//!
+//! let data: [(f32, f32); 3] = [(0.0, 0.0), (0.5, 0.5), (1.0, 1.0)];
+//! assert_eq!(0.5f32, integrate::trapezoidal(&data));
+//! ```
+
+mod adaptive;
+mod columns;
+mod complex;
+mod contour;
+mod cumulative;
+mod error;
+mod extrapolate;
+mod float;
+mod function;
+mod gauss;
+mod kernel;
+mod kronrod;
+mod multi;
+#[cfg(feature = "parallel")]
+mod parallel;
+mod qags;
+mod result;
+mod romberg;
+mod rules;
+mod samples;
+mod simd;
+mod streaming;
+mod tanh_sinh;
+mod triangle;
+mod validate;
+
+pub use self::adaptive::{adaptive_simpson, MAX_ADAPTIVE_DEPTH, MAX_ADAPTIVE_EVALUATIONS};
+#[cfg(feature = "parallel")]
+pub use self::adaptive::par_adaptive_simpson;
+pub use self::cumulative::{cumulative_simpson, cumulative_trapezoidal};
+pub use self::cumulative::{try_cumulative_simpson, try_cumulative_trapezoidal};
+pub use self::columns::{simpson38_dx, simpson38_xy, simpson_auto_dx, simpson_auto_xy, simpson_dx};
+pub use self::columns::{simpson_nonuniform_xy, simpson_strided, simpson_xy, trapezoidal_dx};
+pub use self::columns::{trapezoidal_nonuniform_xy, trapezoidal_strided, trapezoidal_xy, Strided};
+pub use self::columns::{try_simpson38_dx, try_simpson38_xy, try_simpson_auto_dx, try_simpson_auto_xy};
+pub use self::columns::{try_simpson_dx, try_simpson_nonuniform_xy, try_simpson_strided, try_simpson_xy};
+pub use self::columns::{try_trapezoidal_dx, try_trapezoidal_nonuniform_xy, try_trapezoidal_strided};
+pub use self::columns::try_trapezoidal_xy;
+pub use self::complex::Complex;
+pub use self::contour::{contour, quad_complex};
+#[cfg(feature = "parallel")]
+pub use self::contour::par_quad_complex;
+pub use self::error::IntegrationError;
+pub use self::float::{Float, Ordinate};
+pub use self::function::{simpson_fn, trapezoidal_fn};
+pub use self::gauss::{gauss_hermite, gauss_laguerre, gauss_legendre, gauss_legendre_composite};
+pub use self::gauss::{hermite_rule, laguerre_rule, legendre_rule, GaussRule};
+pub use self::kronrod::KronrodRule;
+pub use self::multi::{quad_box, simpson_grid, trapezoidal_grid};
+#[cfg(feature = "parallel")]
+pub use self::parallel::{par_simpson, par_simpson_dx, par_simpson_xy};
+#[cfg(feature = "parallel")]
+pub use self::parallel::{par_trapezoidal, par_trapezoidal_dx, par_trapezoidal_xy};
+pub use self::qags::{quad, quad_with, QuadOptions};
+#[cfg(feature = "parallel")]
+pub use self::qags::par_quad_with;
+pub use self::result::{QuadratureResult, Termination, Tolerance};
+pub use self::romberg::{romberg, RombergOptions, RombergResult, MAX_ROMBERG_LEVEL};
+#[cfg(feature = "parallel")]
+pub use self::romberg::par_romberg;
+pub use self::simd::{simd_simpson_dx, simd_trapezoidal_dx};
+pub use self::streaming::{simpson_iter, simpson_iter_with, trapezoidal_iter, trapezoidal_iter_with, StreamingIntegrator, StreamingRule};
+pub use self::tanh_sinh::{tanh_sinh, TanhSinhOptions, MAX_TANH_SINH_LEVEL};
+#[cfg(feature = "parallel")]
+pub use self::tanh_sinh::par_tanh_sinh;
+pub use self::triangle::{dunavant_rule, quad_mesh, quad_triangle, trapezoidal_mesh};
+pub use self::triangle::{TriangleRule, MAX_DUNAVANT_DEGREE};
+pub use self::validate::{check_spacing, SPACING_TOLERANCE};
+
+use sum::Summation;
+
+/// Computes an integral using the trapezoid rule. 
+/// 
+/// Assumptions: 
+/// 1. That the data is evenly-spaced.
+///
+/// Use `trapezoidal_nonuniform` for data that is not evenly-spaced.
+pub fn trapezoidal<T: Float, Y: Ordinate<T>>(data: &[(T,Y)]) -> Y {
+    rules::trapezoidal(data, Summation::Naive)
+}
+
+/// Computes an integral using the trapezoid rule, using the actual width
+/// of every interval.
+///
+/// Gives the same result as `trapezoidal` (up to rounding) on evenly-spaced
+/// data, at the cost of one multiplication per interval.
+pub fn trapezoidal_nonuniform<T: Float, Y: Ordinate<T>>(data: &[(T,Y)]) -> Y {
+    rules::trapezoidal_nonuniform(data, Summation::Naive)
+}
+
+/// Computes an integral using Simpson's rule. 
+/// 
+/// Assumptions: 
+/// 1. That the data is evenly-spaced.
+/// 2. That there are an odd number of data points (even number of slices).
+/// 3. That there are 3 or more data points.
+///
+/// Use `simpson_auto` for data with an even number of points.
+pub fn simpson<T: Float, Y: Ordinate<T>>(data: &[(T,Y)]) -> Y {
+    rules::simpson(data, Summation::Naive)
+}
+
+/// Computes an integral using Simpson's 3/8 rule. 
+/// 
+/// Assumptions: 
+/// 1. That the data is evenly-spaced.
+/// 2. That the number of slices is a multiple of 3.
+/// 3. That there are 4 or more data points.
+pub fn simpson38<T: Float, Y: Ordinate<T>>(data: &[(T,Y)]) -> Y {
+    rules::simpson38(data, Summation::Naive)
+}
+
+/// Computes an integral using Simpson's rules for any number of points.
+///
+/// An odd number of points is integrated with `simpson`. Otherwise the 1/3
+/// rule is applied to all but the last three slices, which are integrated
+/// with the 3/8 rule, so the result stays fourth-order accurate.
+///
+/// Assumptions: 
+/// 1. That the data is evenly-spaced.
+/// 2. That there are 3 or more data points.
+pub fn simpson_auto<T: Float, Y: Ordinate<T>>(data: &[(T,Y)]) -> Y {
+    rules::simpson_auto(data, Summation::Naive)
+}
+
+/// Computes an integral using Simpson's rule, fitting a quadratic through
+/// each pair of intervals using their actual widths.
+///
+/// Gives the same result as `simpson` (up to rounding) on evenly-spaced
+/// data.
+///
+/// Assumptions: 
+/// 1. That there are an odd number of data points (even number of slices).
+/// 2. That there are 3 or more data points.
+pub fn simpson_nonuniform<T: Float, Y: Ordinate<T>>(data: &[(T,Y)]) -> Y {
+    rules::simpson_nonuniform(data, Summation::Naive)
+}
+
+/// Computes an integral using Simpson's rule for any number of points that
+/// may not be evenly-spaced.
+///
+/// An odd number of points is integrated with `simpson_nonuniform`.
+/// Otherwise the pairs of intervals are integrated as by
+/// `simpson_nonuniform` and the last interval under the quadratic through
+/// the last three points, as `cumulative_simpson` does.
+///
+/// Assumptions:
+/// 1. That there are 3 or more data points.
+///
+/// ```
+/// use oxidize::integrate::simpson_auto_nonuniform;
+///
+/// // y = x^2 at 4 uneven points over [0, 3]
+/// let data: [(f64, f64); 4] = [(0.0, 0.0), (0.5, 0.25), (2.0, 4.0), (3.0, 9.0)];
+/// assert!((simpson_auto_nonuniform(&data) - 9.0).abs() < 1e-14);
+/// ```
+pub fn simpson_auto_nonuniform<T: Float, Y: Ordinate<T>>(data: &[(T,Y)]) -> Y {
+    rules::simpson_auto_nonuniform(data, Summation::Naive)
+}
+
+/// Computes an integral using the trapezoid rule, checking the assumptions
+/// of `trapezoidal` first.
+///
+/// Fails if there are fewer than 2 points, if any coordinate is not finite,
+/// if the abscissae are not strictly increasing or if they are not evenly
+/// spaced.
+pub fn try_trapezoidal<T: Float, Y: Ordinate<T>>(data: &[(T,Y)]) -> Result<Y, IntegrationError> {
+    rules::try_trapezoidal(data)
+}
+
+/// Computes an integral using the trapezoid rule on data that may not be
+/// evenly-spaced, checking its assumptions first.
+///
+/// Fails if there are fewer than 2 points, if any coordinate is not finite
+/// or if the abscissae are not strictly increasing.
+pub fn try_trapezoidal_nonuniform<T: Float, Y: Ordinate<T>>(data: &[(T,Y)]) -> Result<Y, IntegrationError> {
+    rules::try_trapezoidal_nonuniform(data)
+}
+
+/// Computes an integral using Simpson's rule, checking the assumptions of
+/// `simpson` first.
+///
+/// Fails if there are fewer than 3 points or an even number of points, if
+/// any coordinate is not finite, if the abscissae are not strictly
+/// increasing or if they are not evenly spaced.
+pub fn try_simpson<T: Float, Y: Ordinate<T>>(data: &[(T,Y)]) -> Result<Y, IntegrationError> {
+    rules::try_simpson(data)
+}
+
+/// Computes an integral using Simpson's 3/8 rule, checking the assumptions
+/// of `simpson38` first.
+///
+/// Fails if there are fewer than 4 points or the number of slices is not a
+/// multiple of 3, if any coordinate is not finite, if the abscissae are not
+/// strictly increasing or if they are not evenly spaced.
+pub fn try_simpson38<T: Float, Y: Ordinate<T>>(data: &[(T,Y)]) -> Result<Y, IntegrationError> {
+    rules::try_simpson38(data)
+}
+
+/// Computes an integral using Simpson's rules for any number of points,
+/// checking the assumptions of `simpson_auto` first.
+///
+/// Fails if there are fewer than 3 points, if any coordinate is not finite,
+/// if the abscissae are not strictly increasing or if they are not evenly
+/// spaced.
+pub fn try_simpson_auto<T: Float, Y: Ordinate<T>>(data: &[(T,Y)]) -> Result<Y, IntegrationError> {
+    rules::try_simpson_auto(data)
+}
+
+/// Computes an integral using Simpson's rule on data that may not be
+/// evenly-spaced, checking the assumptions of `simpson_nonuniform` first.
+///
+/// Fails if there are fewer than 3 points or an even number of points, if
+/// any coordinate is not finite or if the abscissae are not strictly
+/// increasing.
+pub fn try_simpson_nonuniform<T: Float, Y: Ordinate<T>>(data: &[(T,Y)]) -> Result<Y, IntegrationError> {
+    rules::try_simpson_nonuniform(data)
+}
+
+/// Computes an integral using Simpson's rule for any number of points that
+/// may not be evenly-spaced, checking the assumptions of
+/// `simpson_auto_nonuniform` first.
+///
+/// Fails if there are fewer than 3 points, if any coordinate is not finite
+/// or if the abscissae are not strictly increasing.
+pub fn try_simpson_auto_nonuniform<T: Float, Y: Ordinate<T>>(data: &[(T,Y)]) -> Result<Y, IntegrationError> {
+    rules::try_simpson_auto_nonuniform(data)
+}
+
+/// Computes an integral as `trapezoidal` does, adding up the ordinates
+/// with `summation`.
+///
+/// On millions of samples `Summation::Neumaier` or `Summation::Pairwise`
+/// keep the rounding error of the sum from swamping the rule's own error.
+///
+/// ```
+/// use oxidize::integrate::trapezoidal_with;
+/// use oxidize::sum::Summation;
+///
+/// let data: Vec<(f64,f64)> = (0..1_000_001).map(|i| (i as f64*1e-6, 0.1)).collect();
+/// let area = trapezoidal_with(&data, Summation::Neumaier);
+/// assert!((area - 0.1).abs() < 1e-15);
+/// ```
+pub fn trapezoidal_with<T: Float, Y: Ordinate<T>>(data: &[(T,Y)], summation: Summation) -> Y {
+    rules::trapezoidal(data, summation)
+}
+
+/// Computes an integral as `trapezoidal_nonuniform` does, adding up the
+/// contributions of the intervals with `summation`.
+pub fn trapezoidal_nonuniform_with<T: Float, Y: Ordinate<T>>(data: &[(T,Y)], summation: Summation) -> Y {
+    rules::trapezoidal_nonuniform(data, summation)
+}
+
+/// Computes an integral as `simpson` does, adding up the ordinates with
+/// `summation`.
+pub fn simpson_with<T: Float, Y: Ordinate<T>>(data: &[(T,Y)], summation: Summation) -> Y {
+    rules::simpson(data, summation)
+}
+
+/// Computes an integral as `simpson38` does, adding up the ordinates with
+/// `summation`.
+pub fn simpson38_with<T: Float, Y: Ordinate<T>>(data: &[(T,Y)], summation: Summation) -> Y {
+    rules::simpson38(data, summation)
+}
+
+/// Computes an integral as `simpson_auto` does, adding up the ordinates
+/// with `summation`.
+pub fn simpson_auto_with<T: Float, Y: Ordinate<T>>(data: &[(T,Y)], summation: Summation) -> Y {
+    rules::simpson_auto(data, summation)
+}
+
+/// Computes an integral as `simpson_nonuniform` does, adding up the
+/// contributions of the panels with `summation`.
+pub fn simpson_nonuniform_with<T: Float, Y: Ordinate<T>>(data: &[(T,Y)], summation: Summation) -> Y {
+    rules::simpson_nonuniform(data, summation)
+}
+
+/// Computes an integral as `simpson_auto_nonuniform` does, adding up the
+/// contributions of the pairs of intervals with `summation`.
+pub fn simpson_auto_nonuniform_with<T: Float, Y: Ordinate<T>>(data: &[(T,Y)], summation: Summation) -> Y {
+    rules::simpson_auto_nonuniform(data, summation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trapezoidal_empty_data() {
+        // Empty data
+        let data: [(f64,f64); 0] = [];
+
+        let expected = 0.0;
+        let actual = trapezoidal(&data);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_trapezoidal_one_element() {
+        // Empty data
+        let data: [(f64,f64); 1] = [(0.0, 1.0)];
+
+        let expected = 0.0;
+        let actual = trapezoidal(&data);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_trapezoidal_two_elements() {
+        let data = [(0.0, 0.0), (1.0, 1.0)];
+
+        let expected = 0.5;
+        let actual = trapezoidal(&data);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_trapezoidal_sin_squared() {
+        // This data represents sin^2(x)
+        let data = [
+            (0.0,  0.0), 
+            (0.25, 0.0612087), 
+            (0.5,  0.229849), 
+            (0.75, 0.464631), 
+            (1.0,  0.708073)];
+
+        // Incidentaly, real answer closer to 0.27268
+        let expected = 0.2774313;
+        let actual = trapezoidal(&data);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_trapezoidal_nonuniform_empty_data() {
+        let data: [(f64,f64); 0] = [];
+
+        let expected = 0.0;
+        let actual = trapezoidal_nonuniform(&data);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_trapezoidal_nonuniform_matches_uniform() {
+        // This data represents sin^2(x)
+        let data = [
+            (0.0,  0.0), 
+            (0.25, 0.0612087), 
+            (0.5,  0.229849), 
+            (0.75, 0.464631), 
+            (1.0,  0.708073)];
+
+        let expected = trapezoidal(&data);
+        let actual = trapezoidal_nonuniform(&data);
+
+        assert!((expected - actual).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_trapezoidal_nonuniform_irregular() {
+        // y = x, sampled irregularly; the trapezoid rule is exact for lines
+        let data = [(0.0, 0.0), (0.5, 0.5), (2.0, 2.0), (2.5, 2.5), (4.0, 4.0)];
+
+        let expected = 8.0;
+        let actual = trapezoidal_nonuniform(&data);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_try_trapezoidal_nonuniform_unsorted() {
+        let data = [(0.0, 0.0), (2.0, 1.0), (1.0, 1.0)];
+
+        let expected = Err(IntegrationError::Unsorted { index: 2 });
+        let actual = try_trapezoidal_nonuniform(&data);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_simpson_sin_squared() {
+        // This data represents sin^2(x)
+        let data = [
+            (0.0,  0.0), 
+            (0.25, 0.0612087), 
+            (0.5,  0.229849), 
+            (0.75, 0.464631), 
+            (1.0,  0.708073)];
+
+        // Incidentaly, real answer closer to 0.27268
+        let expected = 0.27259415;
+        let actual = simpson(&data);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_try_trapezoidal_one_element() {
+        let data = [(0.0, 1.0)];
+
+        let expected = Err(IntegrationError::TooFewPoints { required: 2, actual: 1 });
+        let actual = try_trapezoidal(&data);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_try_trapezoidal_two_elements() {
+        let data = [(0.0, 0.0), (1.0, 1.0)];
+
+        let expected = Ok(0.5);
+        let actual = try_trapezoidal(&data);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_try_trapezoidal_non_finite() {
+        let data = [(0.0, 0.0), (1.0, f64::NAN), (2.0, 1.0)];
+
+        let expected = Err(IntegrationError::NonFinite { index: 1 });
+        let actual = try_trapezoidal(&data);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_try_trapezoidal_unsorted() {
+        let data = [(0.0, 0.0), (2.0, 1.0), (1.0, 1.0)];
+
+        let expected = Err(IntegrationError::Unsorted { index: 2 });
+        let actual = try_trapezoidal(&data);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_try_trapezoidal_uneven_spacing() {
+        let data = [(0.0, 0.0), (1.0, 1.0), (3.0, 1.0)];
+
+        let expected = Err(IntegrationError::UnevenSpacing { index: 2 });
+        let actual = try_trapezoidal(&data);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_try_simpson_two_elements() {
+        let data = [(0.0, 0.0), (1.0, 1.0)];
+
+        let expected = Err(IntegrationError::TooFewPoints { required: 3, actual: 2 });
+        let actual = try_simpson(&data);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_try_simpson_even_length() {
+        let data = [(0.0, 0.0), (1.0, 1.0), (2.0, 2.0), (3.0, 3.0)];
+
+        let expected = Err(IntegrationError::WrongParity { actual: 4 });
+        let actual = try_simpson(&data);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_try_simpson_sin_squared() {
+        // This data represents sin^2(x)
+        let data = [
+            (0.0,  0.0), 
+            (0.25, 0.0612087), 
+            (0.5,  0.229849), 
+            (0.75, 0.464631), 
+            (1.0,  0.708073)];
+
+        let expected = Ok(0.27259415);
+        let actual = try_simpson(&data);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_simpson_nonuniform_even_length() {
+        let data = [(0.0, 0.0), (1.0, 1.0), (2.0, 2.0), (3.0, 3.0)];
+
+        let expected = 0.0;
+        let actual = simpson_nonuniform(&data);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_simpson_nonuniform_matches_uniform() {
+        // This data represents sin^2(x)
+        let data = [
+            (0.0,  0.0), 
+            (0.25, 0.0612087), 
+            (0.5,  0.229849), 
+            (0.75, 0.464631), 
+            (1.0,  0.708073)];
+
+        let expected = simpson(&data);
+        let actual = simpson_nonuniform(&data);
+
+        assert!((expected - actual).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_simpson_nonuniform_quadratic_is_exact() {
+        // y = x^2 sampled irregularly over [0, 3]
+        let xs = [0.0, 0.2, 1.0, 1.7, 2.1, 2.3, 3.0];
+        let data: Vec<(f64,f64)> = xs.iter().map(|&x| (x, x*x)).collect();
+
+        let expected = 9.0;
+        let actual = simpson_nonuniform(&data);
+
+        assert!((expected - actual).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_simpson_nonuniform_fourth_order() {
+        // Halving every interval of an irregular grid should cut the error
+        // of a fourth-order rule by about 16.
+        let error = |n: usize| {
+            let data: Vec<(f64,f64)> = (0..n+1)
+                .map(|i| {
+                    let t = i as f64/n as f64;
+                    let x = t + 0.1*(std::f64::consts::PI*t).sin();
+                    (x, x.exp())
+                })
+                .collect();
+            let last = data[n].0;
+            (simpson_nonuniform(&data) - (last.exp() - 1.0)).abs()
+        };
+
+        let ratio = error(16)/error(32);
+
+        assert!(ratio > 14.0 && ratio < 18.0);
+    }
+
+    #[test]
+    fn test_try_simpson_nonuniform_even_length() {
+        let data = [(0.0, 0.0), (1.0, 1.0), (2.5, 2.0), (3.0, 3.0)];
+
+        let expected = Err(IntegrationError::WrongParity { actual: 4 });
+        let actual = try_simpson_nonuniform(&data);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_simpson38_cubic_is_exact() {
+        // y = x^3 over [0, 3]
+        let data = [(0.0, 0.0), (1.0, 1.0), (2.0, 8.0), (3.0, 27.0)];
+
+        let expected = 20.25;
+        let actual = simpson38(&data);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_simpson38_wrong_interval_count() {
+        let data = [(0.0, 0.0), (1.0, 1.0), (2.0, 8.0), (3.0, 27.0), (4.0, 64.0)];
+
+        let expected = 0.0;
+        let actual = simpson38(&data);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_simpson38_two_panels() {
+        // y = x^3 over [0, 6]
+        let data: Vec<(f64,f64)> = (0..7).map(|i| (i as f64, (i*i*i) as f64)).collect();
+
+        let expected = 324.0;
+        let actual = simpson38(&data);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_simpson_auto_odd_matches_simpson() {
+        // This data represents sin^2(x)
+        let data = [
+            (0.0,  0.0), 
+            (0.25, 0.0612087), 
+            (0.5,  0.229849), 
+            (0.75, 0.464631), 
+            (1.0,  0.708073)];
+
+        let expected = simpson(&data);
+        let actual = simpson_auto(&data);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_simpson_auto_even_cubic_is_exact() {
+        // y = x^3 over [0, 5], six points
+        let data: Vec<(f64,f64)> = (0..6).map(|i| (i as f64, (i*i*i) as f64)).collect();
+
+        let expected = 156.25;
+        let actual = simpson_auto(&data);
+
+        assert!((expected - actual).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_simpson_auto_four_points() {
+        let data = [(0.0, 0.0), (1.0, 1.0), (2.0, 8.0), (3.0, 27.0)];
+
+        let expected = simpson38(&data);
+        let actual = simpson_auto(&data);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_try_simpson38_wrong_interval_count() {
+        let data = [(0.0, 0.0), (1.0, 1.0), (2.0, 8.0), (3.0, 27.0), (4.0, 64.0)];
+
+        let expected = Err(IntegrationError::WrongIntervalCount { multiple: 3, actual: 4 });
+        let actual = try_simpson38(&data);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_try_simpson_auto_two_elements() {
+        let data = [(0.0, 0.0), (1.0, 1.0)];
+
+        let expected = Err(IntegrationError::TooFewPoints { required: 3, actual: 2 });
+        let actual = try_simpson_auto(&data);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_trapezoidal_sin_squared_f32() {
+        let data: [(f32,f32); 5] = [
+            (0.0,  0.0), 
+            (0.25, 0.0612087), 
+            (0.5,  0.229849), 
+            (0.75, 0.464631), 
+            (1.0,  0.708073)];
+
+        let expected = 0.2774313f32;
+        let actual = trapezoidal(&data);
+
+        assert!((expected - actual).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_simpson_sin_squared_f32() {
+        let data: [(f32,f32); 5] = [
+            (0.0,  0.0), 
+            (0.25, 0.0612087), 
+            (0.5,  0.229849), 
+            (0.75, 0.464631), 
+            (1.0,  0.708073)];
+
+        let expected = 0.27259415f32;
+        let actual = simpson(&data);
+
+        assert!((expected - actual).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_try_simpson_f32_rounded_spacing() {
+        // Abscissae computed in f32 are too uneven for the f64 tolerance but
+        // within the one widened for f32.
+        let data: Vec<(f32,f32)> = (0..101)
+            .map(|i| {
+                let x = 0.1*i as f32;
+                (x, x*x)
+            })
+            .collect();
+
+        let expected = 1000.0f32/3.0;
+        let actual = try_simpson(&data).unwrap();
+
+        assert!(validate::check_spacing(&data, SPACING_TOLERANCE as f32).is_err());
+        assert!(((expected - actual)/expected).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_simpson_nonuniform_f32_matches_f64() {
+        let data = [(0.0, 1.0), (0.3, 2.0), (1.0, 0.5), (1.2, 4.0), (2.0, 3.0)];
+        let narrow: Vec<(f32,f32)> = data.iter().map(|&(x, y)| (x as f32, y as f32)).collect();
+
+        let expected = simpson_nonuniform(&data);
+        let actual = simpson_nonuniform(&narrow);
+
+        assert!((expected - actual as f64).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_trapezoidal_with_many_samples() {
+        // 2^20 slices of a constant 0.1: the exact sum is 2^20 times 0.1,
+        // which the naive loop misses in the ninth digit.
+        let n = 1 << 20;
+        let data: Vec<(f64,f64)> = (0..n + 1).map(|i| (i as f64, 0.1)).collect();
+
+        let expected = n as f64*0.1;
+        let naive = trapezoidal(&data);
+        let compensated = trapezoidal_with(&data, Summation::Neumaier);
+        let pairwise = trapezoidal_with(&data, Summation::Pairwise);
+
+        assert!((naive - expected).abs() > 1e-8);
+        assert_eq!(expected, compensated);
+        assert!((pairwise - expected).abs() < 1e-3*(naive - expected).abs());
+    }
+
+    #[test]
+    fn test_simpson_with_cancellation() {
+        // Huge ordinates that cancel in pairs hide the small ones from a
+        // naive sum.
+        let ys = [0.0, 0.0, 1e17, 0.0, 1.0, 0.0, -1e17, 0.0, 0.0];
+        let data: Vec<(f64,f64)> = ys.iter().enumerate().map(|(i, &y)| (i as f64, y)).collect();
+
+        let expected = 2.0*(1.0/3.0);
+        let naive = simpson(&data);
+        let exact = simpson_with(&data, Summation::Exact);
+
+        assert!((naive - expected).abs() > 0.5);
+        assert_eq!(expected, exact);
+    }
+
+    #[test]
+    fn test_with_naive_matches_plain() {
+        let data = [(0.0, 1.0), (0.3, 2.0), (1.0, 0.5), (1.2, 4.0), (2.0, 3.0), (2.5, 1.0), (3.0, 2.0)];
+
+        assert_eq!(simpson38(&data), simpson38_with(&data, Summation::Naive));
+        assert_eq!(simpson_auto(&data[..6]), simpson_auto_with(&data[..6], Summation::Naive));
+        assert_eq!(simpson_nonuniform(&data), simpson_nonuniform_with(&data, Summation::Naive));
+        assert_eq!(trapezoidal_nonuniform(&data), trapezoidal_nonuniform_with(&data, Summation::Naive));
+    }
+}
@@ -0,0 +1,366 @@
+//! Integration over rectangles and boxes in any number of dimensions.
+
+use std::cell::{Cell, RefCell};
+
+use sum::Summation;
+
+use super::{kernel, quad_with, Float, IntegrationError, QuadOptions, QuadratureResult, Termination};
+
+/// Computes the integral of gridded data using the trapezoid rule along
+/// every axis.
+///
+/// `values` holds the samples in row-major order, so the last axis varies
+/// fastest, and `shape` gives the number of points along each axis.
+/// `spacing` gives the distance between neighbouring points along each
+/// axis. The grid is reduced one axis at a time, from the last to the
+/// first, with the same arithmetic as `trapezoidal`.
+///
+/// Fails if `shape` and `spacing` differ in length, if `values` does not
+/// hold exactly the number of points `shape` describes, if any axis has
+/// fewer than 2 points or if any value or spacing is not finite.
+///
+/// ```
+/// use oxidize::integrate::trapezoidal_grid;
+///
+/// // f(x, y) = x + y over [0, 1] x [0, 2], sampled at the corners
+/// let values = [0.0, 2.0,
+///               1.0, 3.0];
+/// let area = trapezoidal_grid(&values, &[2, 2], &[1.0, 2.0]).unwrap();
+/// assert_eq!(3.0, area);
+/// ```
+pub fn trapezoidal_grid<T: Float>(values: &[T], shape: &[usize], spacing: &[T]) -> Result<T, IntegrationError> {
+    check_grid(values, shape, spacing, 2)?;
+
+    Ok(reduce(values, shape, spacing, |n, h, y| kernel::trapezoid(Summation::Naive, n, h, y)))
+}
+
+/// Computes the integral of gridded data using Simpson's rules along
+/// every axis.
+///
+/// The layout is as for `trapezoidal_grid`. Each axis is integrated as by
+/// `simpson_auto`, so an axis with an even number of points ends with the
+/// 3/8 rule.
+///
+/// Fails as `trapezoidal_grid` does, except that every axis needs at least
+/// 3 points.
+pub fn simpson_grid<T: Float>(values: &[T], shape: &[usize], spacing: &[T]) -> Result<T, IntegrationError> {
+    check_grid(values, shape, spacing, 3)?;
+
+    Ok(reduce(values, shape, spacing, |n, h, y| kernel::simpson_auto(Summation::Naive, n, h, y)))
+}
+
+fn check_grid<T: Float>(values: &[T], shape: &[usize], spacing: &[T], required: usize) -> Result<(), IntegrationError> {
+    if shape.len() != spacing.len() {
+        return Err(IntegrationError::DimensionMismatch { expected: shape.len(), actual: spacing.len() });
+    }
+    if shape.is_empty() {
+        return Err(IntegrationError::InvalidParameter { name: "shape" });
+    }
+
+    let points = shape.iter().product();
+    if values.len() != points {
+        return Err(IntegrationError::ShapeMismatch { expected: points, actual: values.len() });
+    }
+
+    for &n in shape {
+        if n < required {
+            return Err(IntegrationError::TooFewPoints { required, actual: n });
+        }
+    }
+    if spacing.iter().any(|h| !h.is_finite()) {
+        return Err(IntegrationError::InvalidParameter { name: "spacing" });
+    }
+    if let Some(index) = values.iter().position(|v| !v.is_finite()) {
+        return Err(IntegrationError::NonFinite { index });
+    }
+
+    Ok(())
+}
+
+/// Integrates along the last axis, leaving a grid with one axis fewer,
+/// until a single value is left.
+fn reduce<T, R>(values: &[T], shape: &[usize], spacing: &[T], rule: R) -> T
+    where T: Float, R: Fn(usize, T, &dyn Fn(usize) -> T) -> T
+{
+    let mut current = values.to_vec();
+
+    for (&n, &h) in shape.iter().zip(spacing).rev() {
+        current = current
+            .chunks(n)
+            .map(|fiber| rule(n, h, &|i| fiber[i]))
+            .collect();
+    }
+
+    current[0]
+}
+
+/// Computes the integral of `f` over the box `[lower[0], upper[0]] x ...`
+/// by nesting `quad_with` along every axis.
+///
+/// The first axis is integrated outermost. Every inner integral uses the
+/// same options as the outer one, and bounds may be infinite. The error
+/// estimate adds, for every level of nesting, the magnitude of the outer
+/// integral times the error of the inner integrals relative to their
+/// magnitude, both summed over the points the outer integral evaluates
+/// them at. The termination is the first one that was not `Converged`, if
+/// any.
+///
+/// Fails if `lower` and `upper` differ in length or are empty, if any
+/// bound is NaN or if the options are invalid, or with the error of the
+/// first inner integral that fails.
+///
+/// ```
+/// use oxidize::integrate::{quad_box, QuadOptions};
+///
+/// let f = |p: &[f64]| p[0]*p[1];
+/// let result = quad_box(f, &[0.0, 0.0], &[1.0, 2.0], QuadOptions::default()).unwrap();
+/// assert!((result.value - 1.0).abs() < 1e-14);
+/// ```
+pub fn quad_box<F: Fn(&[f64]) -> f64>(f: F, lower: &[f64], upper: &[f64], options: QuadOptions) -> Result<QuadratureResult, IntegrationError> {
+    if lower.len() != upper.len() {
+        return Err(IntegrationError::DimensionMismatch { expected: lower.len(), actual: upper.len() });
+    }
+    if lower.is_empty() {
+        return Err(IntegrationError::InvalidParameter { name: "lower" });
+    }
+    if lower.iter().chain(upper).any(|x| x.is_nan()) {
+        return Err(IntegrationError::NonFiniteBounds);
+    }
+
+    let evaluations = Cell::new(0);
+    let f = |x: &[f64]| {
+        evaluations.set(evaluations.get() + 1);
+        f(x)
+    };
+
+    let nest = Nest {
+        f: &f,
+        lower,
+        upper,
+        options,
+        point: RefCell::new(vec![0.0; lower.len()]),
+        termination: Cell::new(Termination::Converged),
+        failure: Cell::new(None),
+    };
+    let mut result = nest.integrate(0)?;
+
+    result.evaluations = evaluations.get();
+    if result.termination == Termination::Converged {
+        result.termination = nest.termination.get();
+    }
+    Ok(result)
+}
+
+/// The state shared by the nested integrals of `quad_box`.
+struct Nest<'a, F: 'a> {
+    f: &'a F,
+    lower: &'a [f64],
+    upper: &'a [f64],
+    options: QuadOptions,
+    point: RefCell<Vec<f64>>,
+    termination: Cell<Termination>,
+    failure: Cell<Option<IntegrationError>>,
+}
+
+impl<'a, F: Fn(&[f64]) -> f64> Nest<'a, F> {
+    /// Integrates over axis `axis` and, nested inside, every later axis,
+    /// with the earlier coordinates of `point` held fixed.
+    fn integrate(&self, axis: usize) -> Result<QuadratureResult, IntegrationError> {
+        let last = axis + 1 == self.lower.len();
+        let inner_error = Cell::new(0.0f64);
+        let inner_magnitude = Cell::new(0.0f64);
+
+        let g = |x: f64| {
+            // Once an inner integral has failed, the outer one fails too.
+            if self.failure.get().is_some() {
+                return f64::NAN;
+            }
+
+            self.point.borrow_mut()[axis] = x;
+            if last {
+                return (self.f)(&self.point.borrow());
+            }
+
+            match self.integrate(axis + 1) {
+                Ok(inner) => {
+                    inner_error.set(inner_error.get() + inner.error);
+                    inner_magnitude.set(inner_magnitude.get() + inner.value.abs());
+                    if inner.termination != Termination::Converged
+                        && self.termination.get() == Termination::Converged {
+                        self.termination.set(inner.termination);
+                    }
+                    inner.value
+                }
+                Err(error) => {
+                    self.failure.set(Some(error));
+                    f64::NAN
+                }
+            }
+        };
+
+        let outer = quad_with(g, self.lower[axis], self.upper[axis], self.options);
+        if let Some(error) = self.failure.get() {
+            return Err(error);
+        }
+
+        // The width of the interval would make the error infinite on an
+        // infinite axis, so the inner errors are weighted by magnitude.
+        let mut result = outer?;
+        if inner_error.get() > 0.0 {
+            let relative = inner_error.get()/inner_magnitude.get();
+            result.error += if relative.is_finite() { relative*result.value.abs() } else { inner_error.get() };
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use integrate::{simpson, Tolerance};
+
+    #[test]
+    fn test_trapezoidal_grid_bilinear_is_exact() {
+        // f(x, y) = x*y over [0, 2] x [0, 1] on a 3 x 5 grid
+        let xs = [0.0, 1.0, 2.0];
+        let ys = [0.0, 0.25, 0.5, 0.75, 1.0];
+        let values: Vec<f64> = xs.iter().flat_map(|x| ys.iter().map(move |y| x*y)).collect();
+
+        let expected = Ok(1.0);
+        let actual = trapezoidal_grid(&values, &[3, 5], &[1.0, 0.25]);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_trapezoidal_grid_bilinear_f32() {
+        let xs = [0.0f32, 1.0, 2.0];
+        let ys = [0.0f32, 0.25, 0.5, 0.75, 1.0];
+        let values: Vec<f32> = xs.iter().flat_map(|x| ys.iter().map(move |y| x*y)).collect();
+
+        let expected = Ok(1.0f32);
+        let actual = trapezoidal_grid(&values, &[3, 5], &[1.0, 0.25]);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_simpson_grid_one_axis_matches_simpson() {
+        // This data represents sin^2(x)
+        let data = [
+            (0.0,  0.0), 
+            (0.25, 0.0612087), 
+            (0.5,  0.229849), 
+            (0.75, 0.464631), 
+            (1.0,  0.708073)];
+        let values: Vec<f64> = data.iter().map(|p| p.1).collect();
+
+        let expected = Ok(simpson(&data));
+        let actual = simpson_grid(&values, &[5], &[0.25]);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_simpson_grid_three_dimensions() {
+        // f(x, y, z) = x^2 y z^3 over [0, 1] x [0, 2] x [0, 3], with an even
+        // number of points along z; exact for these degrees
+        let (nx, ny, nz) = (3, 5, 4);
+        let mut values = Vec::new();
+        for i in 0..nx {
+            for j in 0..ny {
+                for k in 0..nz {
+                    let (x, y, z) = (0.5*i as f64, 0.5*j as f64, k as f64);
+                    values.push(x*x*y*z*z*z);
+                }
+            }
+        }
+
+        let expected = (1.0/3.0)*2.0*(81.0/4.0);
+        let actual = simpson_grid(&values, &[nx, ny, nz], &[0.5, 0.5, 1.0]).unwrap();
+
+        assert!((expected - actual).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_trapezoidal_grid_shape_mismatch() {
+        let values = [0.0; 5];
+
+        let expected = Err(IntegrationError::ShapeMismatch { expected: 6, actual: 5 });
+        let actual = trapezoidal_grid(&values, &[2, 3], &[1.0, 1.0]);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_trapezoidal_grid_dimension_mismatch() {
+        let values = [0.0; 6];
+
+        let expected = Err(IntegrationError::DimensionMismatch { expected: 2, actual: 1 });
+        let actual = trapezoidal_grid(&values, &[2, 3], &[1.0]);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_simpson_grid_short_axis() {
+        let values = [0.0; 6];
+
+        let expected = Err(IntegrationError::TooFewPoints { required: 3, actual: 2 });
+        let actual = simpson_grid(&values, &[2, 3], &[1.0, 1.0]);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_quad_box_exponential_cube() {
+        let f = |p: &[f64]| (p[0] + p[1] + p[2]).exp();
+        let expected = (1f64.exp() - 1.0).powi(3);
+        let result = quad_box(f, &[0.0; 3], &[1.0; 3], QuadOptions::default()).unwrap();
+
+        assert!(result.converged());
+        assert!((expected - result.value).abs() < 1e-12);
+        assert_eq!(21*21*21, result.evaluations);
+    }
+
+    #[test]
+    fn test_quad_box_gaussian_plane() {
+        // The integral of exp(-x^2 - y^2) over the whole plane is pi
+        let f = |p: &[f64]| (-p[0]*p[0] - p[1]*p[1]).exp();
+        let infinity = f64::INFINITY;
+        let options = QuadOptions { tolerance: Tolerance::absolute(1e-10), ..QuadOptions::default() };
+        let result = quad_box(f, &[-infinity, -infinity], &[infinity, infinity], options).unwrap();
+
+        assert!((std::f64::consts::PI - result.value).abs() < 1e-9);
+        assert!(result.error.is_finite());
+        assert!(result.error < 1e-8);
+    }
+
+    #[test]
+    fn test_quad_box_semi_infinite_error() {
+        // The integral of exp(-x) y^2 over [0, inf) x [0, 1] is 1/3
+        let f = |p: &[f64]| (-p[0]).exp()*p[1]*p[1];
+        let result = quad_box(f, &[0.0, 0.0], &[f64::INFINITY, 1.0], QuadOptions::default()).unwrap();
+
+        assert!(result.converged());
+        assert!(result.error.is_finite());
+        assert!((result.value - 1.0/3.0).abs() <= result.error.max(1e-14));
+    }
+
+    #[test]
+    fn test_quad_box_inner_failure() {
+        // The innermost integrand is NaN across part of the box
+        let f = |p: &[f64]| if p[1] > 0.5 { f64::NAN } else { p[0] };
+        let result = quad_box(f, &[0.0, 0.0], &[1.0, 1.0], QuadOptions::default());
+
+        assert_eq!(Err(IntegrationError::NonFiniteIntegrand), result);
+    }
+
+    #[test]
+    fn test_quad_box_dimension_mismatch() {
+        let expected = Err(IntegrationError::DimensionMismatch { expected: 2, actual: 1 });
+        let actual = quad_box(|p: &[f64]| p[0], &[0.0, 0.0], &[1.0], QuadOptions::default());
+
+        assert_eq!(expected, actual);
+    }
+}
@@ -0,0 +1,278 @@
+//! Rules that spread their work over several threads.
+//!
+//! The sample-based rules split the slices into runs of
+//! `sum::PARALLEL_CHUNK`, apply the same kernel as the serial rules to
+//! each run on its own, and add the runs' integrals in order with the
+//! given `Summation`. Where the runs are split does not depend on the
+//! number of threads, so neither do the results.
+//!
+//! The function-based rules evaluate the integrand on several threads and
+//! add the values in the same order as their serial forms, so their
+//! results are the serial ones.
+
+use rayon::prelude::*;
+
+use sum::{Summation, PARALLEL_CHUNK};
+
+use super::function::{Counted, Points};
+use super::columns::paired;
+use super::samples::{Samples, Uniform};
+use super::{kernel, Float, Ordinate};
+
+/// Computes an integral as `trapezoidal_with` does, on several threads.
+///
+/// The result is the same however many threads run, and matches
+/// `trapezoidal_with` exactly for up to `sum::PARALLEL_CHUNK` slices.
+///
+/// ```
+/// use oxidize::integrate::{par_trapezoidal, trapezoidal};
+/// use oxidize::sum::Summation;
+///
+/// let data: Vec<(f64,f64)> = (0..1_000_001).map(|i| (i as f64*1e-6, 2.0)).collect();
+/// let area = par_trapezoidal(&data, Summation::Pairwise);
+/// assert!((area - trapezoidal(&data)).abs() < 1e-9);
+/// ```
+pub fn par_trapezoidal<T, Y>(data: &[(T,Y)], summation: Summation) -> Y
+    where T: Float + Sync, Y: Ordinate<T> + Send + Sync
+{
+    trapezoid_runs(data, summation)
+}
+
+/// Computes an integral as `par_trapezoidal` does, over separate abscissae
+/// and ordinates.
+///
+/// Panics if `xs` and `ys` differ in length.
+pub fn par_trapezoidal_xy<T, Y>(xs: &[T], ys: &[Y], summation: Summation) -> Y
+    where T: Float + Sync, Y: Ordinate<T> + Send + Sync
+{
+    trapezoid_runs(&paired(xs, ys), summation)
+}
+
+/// Computes an integral as `par_trapezoidal` does, over ordinates sampled
+/// every `dx`.
+pub fn par_trapezoidal_dx<T, Y>(dx: T, ys: &[Y], summation: Summation) -> Y
+    where T: Float + Sync, Y: Ordinate<T> + Send + Sync
+{
+    trapezoid_runs(&Uniform { dx, ys }, summation)
+}
+
+/// Computes an integral as `simpson_with` does, on several threads.
+///
+/// The result is the same however many threads run, and matches
+/// `simpson_with` exactly for up to `sum::PARALLEL_CHUNK` slices.
+pub fn par_simpson<T, Y>(data: &[(T,Y)], summation: Summation) -> Y
+    where T: Float + Sync, Y: Ordinate<T> + Send + Sync
+{
+    simpson_runs(data, summation)
+}
+
+/// Computes an integral as `par_simpson` does, over separate abscissae and
+/// ordinates.
+///
+/// Panics if `xs` and `ys` differ in length.
+pub fn par_simpson_xy<T, Y>(xs: &[T], ys: &[Y], summation: Summation) -> Y
+    where T: Float + Sync, Y: Ordinate<T> + Send + Sync
+{
+    simpson_runs(&paired(xs, ys), summation)
+}
+
+/// Computes an integral as `par_simpson` does, over ordinates sampled every
+/// `dx`.
+///
+/// ```
+/// use oxidize::integrate::{par_simpson_dx, simpson_dx};
+/// use oxidize::sum::Summation;
+///
+/// let ys: Vec<f64> = (0..1_000_001).map(|i| (i as f64*1e-6).exp()).collect();
+/// let area = par_simpson_dx(1e-6, &ys, Summation::Neumaier);
+/// assert!((area - simpson_dx(1e-6, &ys)).abs() < 1e-12);
+/// ```
+pub fn par_simpson_dx<T, Y>(dx: T, ys: &[Y], summation: Summation) -> Y
+    where T: Float + Sync, Y: Ordinate<T> + Send + Sync
+{
+    simpson_runs(&Uniform { dx, ys }, summation)
+}
+
+/// Returns the sum of `f(x)*weight` over the first `n` of `points` as
+/// `weighted_sum` does, evaluating `f` on several threads a run of
+/// `sum::PARALLEL_CHUNK` points at a time.
+pub fn par_weighted_sum<Y, F>(f: &Counted<F>, n: usize, points: Points) -> Y
+    where Y: Ordinate<f64> + Send, F: Fn(f64) -> Y + Sync
+{
+    let mut result = Y::zero();
+    for start in (0..n).step_by(PARALLEL_CHUNK) {
+        let end = n.min(start + PARALLEL_CHUNK);
+        let values: Vec<Option<Y>> = (start..end).into_par_iter()
+            .map(|i| points(i).map(|(x, weight)| f.call(x)*weight))
+            .collect();
+
+        for value in values.into_iter().flatten() {
+            result += value;
+        }
+    }
+    result
+}
+
+/// The trapezoid rule over `data`, a run of slices at a time.
+///
+/// Assumptions:
+/// 1. That the abscissae are evenly spaced.
+fn trapezoid_runs<T, Y, S>(data: &S, summation: Summation) -> Y
+    where T: Float + Sync, Y: Ordinate<T> + Send + Sync, S: Samples<T, Y> + Sync + ?Sized
+{
+    if data.len() <= 1 {
+        return Y::zero();
+    }
+
+    let h = data.x(1) - data.x(0);
+    over_runs(summation, data.len() - 1, |first, n| kernel::trapezoid(summation, n, h, |i| data.y(first + i)))
+}
+
+/// Simpson's rule over `data`, a run of slices at a time.
+///
+/// Assumptions:
+/// 1. That the abscissae are evenly spaced.
+fn simpson_runs<T, Y, S>(data: &S, summation: Summation) -> Y
+    where T: Float + Sync, Y: Ordinate<T> + Send + Sync, S: Samples<T, Y> + Sync + ?Sized
+{
+    if data.len() <= 2 || data.len().is_multiple_of(2) {
+        return Y::zero();
+    }
+
+    // The runs hold an even number of slices, so each is a whole Simpson
+    // rule.
+    let h = data.x(1) - data.x(0);
+    over_runs(summation, data.len() - 1, |first, n| kernel::simpson(summation, n, h, |i| data.y(first + i)))
+}
+
+/// Applies `kernel`, given its first point and number of points, to runs
+/// of `PARALLEL_CHUNK` slices on several threads, and adds up the runs'
+/// integrals in order with `summation`. Neighbouring runs share an end
+/// point.
+fn over_runs<T, Y, K>(summation: Summation, slices: usize, kernel: K) -> Y
+    where T: Float, Y: Ordinate<T> + Send, K: Fn(usize, usize) -> Y + Sync
+{
+    if slices <= PARALLEL_CHUNK {
+        return kernel(0, slices + 1);
+    }
+
+    let runs = slices.div_ceil(PARALLEL_CHUNK);
+    let partials: Vec<Y> = (0..runs).into_par_iter().map(|run| {
+        let first = run*PARALLEL_CHUNK;
+        kernel(first, PARALLEL_CHUNK.min(slices - first) + 1)
+    }).collect();
+
+    summation.sum(Y::zero(), runs, |k| partials[k])
+}
+
+#[cfg(test)]
+mod tests {
+    use rayon;
+
+    use super::*;
+    use integrate::{adaptive_simpson, par_adaptive_simpson, par_quad_complex, par_quad_with, par_romberg, par_tanh_sinh};
+    use integrate::{quad_complex, quad_with, romberg, simpson_with, tanh_sinh, trapezoidal_with, Complex};
+    use integrate::{QuadOptions, RombergOptions, TanhSinhOptions, Tolerance};
+
+    fn samples(n: usize) -> Vec<(f64, f64)> {
+        let h = 1.0/(n - 1) as f64;
+        (0..n).map(|i| {
+            let x = i as f64*h;
+            (x, (3.0*x).sin() + 0.1)
+        }).collect()
+    }
+
+    fn on_threads<R: Send, F: FnOnce() -> R + Send>(threads: usize, f: F) -> R {
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(threads).build().unwrap();
+        pool.install(f)
+    }
+
+    #[test]
+    fn test_par_rules_reproducible() {
+        let data = samples(1_000_001);
+
+        for &summation in &[Summation::Naive, Summation::Neumaier, Summation::Pairwise] {
+            let trapezoid = on_threads(1, || par_trapezoidal(&data, summation));
+            let simpson = on_threads(1, || par_simpson(&data, summation));
+
+            for &threads in &[2, 3, 8] {
+                assert_eq!(trapezoid, on_threads(threads, || par_trapezoidal(&data, summation)));
+                assert_eq!(simpson, on_threads(threads, || par_simpson(&data, summation)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_par_rules_match_serial() {
+        let large = samples(1_000_001);
+        let small = samples(1001);
+
+        for &summation in &[Summation::Naive, Summation::Kahan, Summation::Exact] {
+            let serial = trapezoidal_with(&large, summation);
+            assert!((serial - par_trapezoidal(&large, summation)).abs() < 1e-12);
+            let serial = simpson_with(&large, summation);
+            assert!((serial - par_simpson(&large, summation)).abs() < 1e-12);
+
+            assert_eq!(trapezoidal_with(&small, summation), par_trapezoidal(&small, summation));
+            assert_eq!(simpson_with(&small, summation), par_simpson(&small, summation));
+        }
+    }
+
+    #[test]
+    fn test_par_rules_columns_match_pairs() {
+        // One slice past a whole number of runs leaves a last run of one
+        // slice for the trapezoid rule and two for Simpson's.
+        for &n in &[1001, 2*PARALLEL_CHUNK + 2, 2*PARALLEL_CHUNK + 3] {
+            let data = samples(n);
+            let dx = data[1].0;
+            let xs: Vec<f64> = data.iter().map(|&(x, _)| x).collect();
+            let ys: Vec<f64> = data.iter().map(|&(_, y)| y).collect();
+
+            let trapezoid = par_trapezoidal(&data, Summation::Kahan);
+            assert_eq!(trapezoid, par_trapezoidal_xy(&xs, &ys, Summation::Kahan));
+            assert_eq!(trapezoid, par_trapezoidal_dx(dx, &ys, Summation::Kahan));
+
+            let simpson = par_simpson(&data, Summation::Kahan);
+            assert_eq!(simpson, par_simpson_xy(&xs, &ys, Summation::Kahan));
+            assert_eq!(simpson, par_simpson_dx(dx, &ys, Summation::Kahan));
+            assert!((simpson - simpson_with(&data, Summation::Kahan)).abs() < 1e-12, "{}", n);
+        }
+    }
+
+    #[test]
+    fn test_par_rules_too_few_points() {
+        assert_eq!(0.0, par_trapezoidal(&[(0.0, 1.0)], Summation::Naive));
+        assert_eq!(0.0, par_simpson(&[(0.0, 1.0), (1.0, 1.0)], Summation::Naive));
+    }
+
+    #[test]
+    fn test_par_quad_matches_serial() {
+        let options = QuadOptions::default();
+        let f = |x: f64| 1.0/(1e-4 + (x - 0.3)*(x - 0.3)) + (-x*x).exp();
+
+        for &(a, b) in &[(0.0, 1.0), (1.0, 0.0), (0.0, f64::INFINITY), (f64::NEG_INFINITY, f64::INFINITY)] {
+            let serial = quad_with(f, a, b, options).unwrap();
+            let parallel = on_threads(4, || par_quad_with(f, a, b, options).unwrap());
+
+            assert_eq!(serial, parallel);
+        }
+    }
+
+    #[test]
+    fn test_par_adaptive_rules_match_serial() {
+        let f = |x: f64| x.ln()/x.sqrt() + 1.0/(1e-4 + (x - 0.3)*(x - 0.3));
+        let g = |x: f64| Complex::from_polar(1.0/x.sqrt(), 5.0*x);
+
+        let simpson = adaptive_simpson(f, 1e-9, 1.0, Tolerance::relative(1e-10)).unwrap();
+        let romberg = romberg(f64::exp, 0.0, 1.0, RombergOptions::default()).unwrap();
+        let tanh = tanh_sinh(f, 0.0, 1.0, TanhSinhOptions::default()).unwrap();
+        let complex = quad_complex(g, 0.0, 1.0, QuadOptions::default()).unwrap();
+
+        for &threads in &[1, 4] {
+            assert_eq!(simpson, on_threads(threads, || par_adaptive_simpson(f, 1e-9, 1.0, Tolerance::relative(1e-10)).unwrap()));
+            assert_eq!(romberg, on_threads(threads, || par_romberg(f64::exp, 0.0, 1.0, RombergOptions::default()).unwrap()));
+            assert_eq!(tanh, on_threads(threads, || par_tanh_sinh(f, 0.0, 1.0, TanhSinhOptions::default()).unwrap()));
+            assert_eq!(complex, on_threads(threads, || par_quad_complex(g, 0.0, 1.0, QuadOptions::default()).unwrap()));
+        }
+    }
+}
@@ -0,0 +1,689 @@
+//! Globally adaptive Gauss–Kronrod quadrature with extrapolation, after
+//! QUADPACK's `dqags`.
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::ops::Neg;
+use std::sync::atomic::{self, AtomicBool, AtomicUsize};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use super::extrapolate::{Extrapolation, Scalar};
+use super::kronrod::{self, Estimate, KronrodRule};
+use super::{IntegrationError, Ordinate, QuadratureResult, Termination, Tolerance};
+
+/// The number of bisections in a row that may fail to reduce the error
+/// before rounding error is blamed.
+const MAX_STALLED: usize = 10;
+
+/// The number of extrapolations in a row that may fail to improve the
+/// result before the integral is considered divergent.
+const MAX_FUTILE_EXTRAPOLATIONS: usize = 5;
+
+/// Options for `quad_with`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuadOptions {
+    /// The accuracy at which to stop refining.
+    pub tolerance: Tolerance,
+
+    /// The Gauss–Kronrod pair applied to every subinterval.
+    pub rule: KronrodRule,
+
+    /// The most subintervals the interval may be split into.
+    pub max_subdivisions: usize,
+}
+
+impl Default for QuadOptions {
+    fn default() -> QuadOptions {
+        QuadOptions {
+            tolerance: Tolerance::default(),
+            rule: KronrodRule::Gk21,
+            max_subdivisions: 200,
+        }
+    }
+}
+
+/// Computes the integral of `f` over `[a, b]` with the default options.
+///
+/// This is the rule to reach for when integrating a function: it adapts to
+/// the integrand, copes with integrable singularities at the ends of the
+/// interval and with infinite bounds, and reports how it stopped. See
+/// `quad_with`.
+///
+/// ```
+/// use oxidize::integrate::quad;
+///
+/// let result = quad(|x: f64| 1.0/x.sqrt(), 0.0, 1.0).unwrap();
+/// assert!(result.converged());
+/// assert!((result.value - 2.0).abs() < 1e-10);
+/// ```
+pub fn quad<F: Fn(f64) -> f64>(f: F, a: f64, b: f64) -> Result<QuadratureResult, IntegrationError> {
+    quad_with(f, a, b, QuadOptions::default())
+}
+
+/// Computes the integral of `f` over `[a, b]` using globally adaptive
+/// Gauss–Kronrod quadrature.
+///
+/// The subinterval with the largest error estimate is bisected until the
+/// total error meets the tolerance. When the largest errors come from ever
+/// smaller subintervals, as they do near a singularity, the sequence of
+/// estimates is accelerated with Wynn's epsilon algorithm and the
+/// extrapolated value is returned if it is more accurate.
+///
+/// Either bound may be infinite. An infinite interval is mapped onto
+/// `[0, 1)` with `x = a + t/(1 - t)`, or `x = b - t/(1 - t)` when the lower
+/// bound is infinite; over the whole real line the two halves `x >= 0` and
+/// `x <= 0` are mapped this way and integrated together.
+///
+/// Refining stops with `Termination::Divergent` when the extrapolations
+/// keep failing and the estimates either keep growing by steps that do not
+/// shrink or disagree wildly with the extrapolated limit, and also when an
+/// estimate overflows. Only finite estimates are reported as converged.
+///
+/// Fails if either bound is NaN, if the tolerance is invalid, if
+/// `max_subdivisions` is 0 or if `f` returns NaN or an infinite value at a
+/// point it is evaluated at.
+///
+/// ```
+/// use oxidize::integrate::{quad_with, QuadOptions};
+///
+/// let f = |x: f64| (-x*x).exp();
+/// let result = quad_with(f, f64::NEG_INFINITY, f64::INFINITY, QuadOptions::default()).unwrap();
+/// assert!((result.value - std::f64::consts::PI.sqrt()).abs() < 1e-10);
+/// ```
+pub fn quad_with<F: Fn(f64) -> f64>(f: F, a: f64, b: f64, options: QuadOptions) -> Result<QuadratureResult, IntegrationError> {
+    solve(&f, a, b, options, |integrand, a, b| Solver::new(integrand, a, b, options).run())
+}
+
+/// Computes the integral of `f` over `[a, b]` as `quad_with` does,
+/// evaluating `f` on several threads.
+///
+/// The points of every Gauss–Kronrod estimate, and of both halves of every
+/// bisection, are evaluated in parallel; the estimates are then combined
+/// in the same order as by `quad_with`, so the result is identical to it.
+/// This pays off when `f` is expensive, since each round of evaluations is
+/// only 30 to 42 calls.
+///
+/// Fails as `quad_with` does.
+///
+/// ```
+/// use oxidize::integrate::{par_quad_with, quad_with, QuadOptions};
+///
+/// let f = |x: f64| x.ln()/x.sqrt();
+/// let serial = quad_with(f, 0.0, 1.0, QuadOptions::default()).unwrap();
+/// let parallel = par_quad_with(f, 0.0, 1.0, QuadOptions::default()).unwrap();
+/// assert_eq!(serial, parallel);
+/// ```
+#[cfg(feature = "parallel")]
+pub fn par_quad_with<F: Fn(f64) -> f64 + Sync>(f: F, a: f64, b: f64, options: QuadOptions) -> Result<QuadratureResult, IntegrationError> {
+    solve(&f, a, b, options, |integrand, a, b| Solver::new(&Parallel(integrand), a, b, options).run())
+}
+
+/// Checks the arguments, orients and maps the interval, and hands the
+/// mapped integrand to `run`.
+///
+/// The integrand may be real or complex; a complex one is refined in a
+/// single pass, with the modulus of every estimate as its error.
+pub fn solve<V, F, R>(f: &F, a: f64, b: f64, options: QuadOptions, run: R) -> Result<QuadratureResult<V>, IntegrationError>
+    where V: Scalar + Neg<Output = V>,
+          F: Fn(f64) -> V,
+          R: FnOnce(&Integrand<F>, f64, f64) -> (V, f64, Termination)
+{
+    if a.is_nan() || b.is_nan() {
+        return Err(IntegrationError::NonFiniteBounds);
+    }
+    options.tolerance.check()?;
+    if options.max_subdivisions < 1 {
+        return Err(IntegrationError::InvalidParameter { name: "max_subdivisions" });
+    }
+
+    let reversed = b < a;
+    let (a, b) = if reversed { (b, a) } else { (a, b) };
+
+    if a == b {
+        return Ok(QuadratureResult { value: V::zero(), error: 0.0, evaluations: 0, termination: Termination::Converged });
+    }
+
+    let (map, lower, upper) = if a.is_finite() && b.is_finite() {
+        (Map::Identity, a, b)
+    } else if a.is_finite() {
+        (Map::FromLower(a), 0.0, 1.0)
+    } else if b.is_finite() {
+        (Map::FromUpper(b), 0.0, 1.0)
+    } else {
+        (Map::WholeLine, 0.0, 1.0)
+    };
+
+    let integrand = Integrand { f, map, evaluations: AtomicUsize::new(0), non_finite: AtomicBool::new(false) };
+    let (value, error, termination) = run(&integrand, lower, upper);
+    if integrand.non_finite.into_inner() {
+        return Err(IntegrationError::NonFiniteIntegrand);
+    }
+    let value = if reversed { -value } else { value };
+
+    Ok(QuadratureResult { value, error, evaluations: integrand.evaluations.into_inner(), termination })
+}
+
+/// How an interval is mapped onto the one the solver works on.
+#[derive(Debug, Clone, Copy)]
+enum Map {
+    Identity,
+
+    /// `[a, inf)` onto `[0, 1)`.
+    FromLower(f64),
+
+    /// `(-inf, b]` onto `[0, 1)`.
+    FromUpper(f64),
+
+    /// Both halves of the real line onto `[0, 1)`.
+    WholeLine,
+}
+
+/// The integrand after mapping, counting the calls to `f` and noting
+/// whether any returned a value that is not finite.
+pub struct Integrand<'a, F: 'a> {
+    f: &'a F,
+    map: Map,
+    evaluations: AtomicUsize,
+    non_finite: AtomicBool,
+}
+
+impl<'a, V: Ordinate<f64>, F: Fn(f64) -> V> Integrand<'a, F> {
+    fn eval(&self, t: f64) -> V {
+        match self.map {
+            Map::Identity => self.call(t),
+            Map::FromLower(a) => {
+                let s = 1.0 - t;
+                self.call(a + t/s)/(s*s)
+            }
+            Map::FromUpper(b) => {
+                let s = 1.0 - t;
+                self.call(b - t/s)/(s*s)
+            }
+            Map::WholeLine => {
+                let s = 1.0 - t;
+                let x = t/s;
+                (self.call(x) + self.call(-x))/(s*s)
+            }
+        }
+    }
+
+    fn call(&self, x: f64) -> V {
+        self.evaluations.fetch_add(1, atomic::Ordering::Relaxed);
+        let value = (self.f)(x);
+        if !value.is_finite() {
+            self.non_finite.store(true, atomic::Ordering::Relaxed);
+        }
+        value
+    }
+}
+
+/// A way of evaluating the integrand at the points of a rule.
+pub trait Evaluate {
+    /// The values of the integrand.
+    type Value: Scalar;
+
+    fn estimate(&self, rule: KronrodRule, a: f64, b: f64) -> Estimate<Self::Value>;
+
+    /// Applies `rule` over `[a, mid]` and over `[mid, b]`.
+    fn halves(&self, rule: KronrodRule, a: f64, mid: f64, b: f64) -> (Estimate<Self::Value>, Estimate<Self::Value>) {
+        (self.estimate(rule, a, mid), self.estimate(rule, mid, b))
+    }
+}
+
+impl<'a, V: Scalar, F: Fn(f64) -> V> Evaluate for Integrand<'a, F> {
+    type Value = V;
+
+    fn estimate(&self, rule: KronrodRule, a: f64, b: f64) -> Estimate<V> {
+        kronrod::estimate(rule, &|t| self.eval(t), a, b)
+    }
+}
+
+/// Evaluates the integrand at all the points of a round at once, on
+/// rayon's threads.
+#[cfg(feature = "parallel")]
+pub struct Parallel<'r, 'a: 'r, F: 'a>(pub &'r Integrand<'a, F>);
+
+#[cfg(feature = "parallel")]
+impl<'r, 'a, V: Scalar + Send, F: Fn(f64) -> V + Sync> Evaluate for Parallel<'r, 'a, F> {
+    type Value = V;
+
+    fn estimate(&self, rule: KronrodRule, a: f64, b: f64) -> Estimate<V> {
+        let points = kronrod::abscissae(rule, a, b);
+        let values: Vec<V> = points[..rule.points()].par_iter().map(|&t| self.0.eval(t)).collect();
+
+        kronrod::combine(rule, a, b, &values)
+    }
+
+    fn halves(&self, rule: KronrodRule, a: f64, mid: f64, b: f64) -> (Estimate<V>, Estimate<V>) {
+        let n = rule.points();
+        let mut points = kronrod::abscissae(rule, a, mid)[..n].to_vec();
+        points.extend_from_slice(&kronrod::abscissae(rule, mid, b)[..n]);
+        let values: Vec<V> = points.par_iter().map(|&t| self.0.eval(t)).collect();
+
+        (kronrod::combine(rule, a, mid, &values[..n]), kronrod::combine(rule, mid, b, &values[n..]))
+    }
+}
+
+/// A subinterval and the Gauss–Kronrod estimate over it.
+#[derive(Debug, Clone, Copy)]
+struct Segment<V> {
+    a: f64,
+    b: f64,
+    area: V,
+    error: f64,
+    depth: usize,
+}
+
+// Segments are ordered by their error so the heap yields the worst first.
+impl<V> PartialEq for Segment<V> {
+    fn eq(&self, other: &Segment<V>) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<V> Eq for Segment<V> {}
+
+impl<V> PartialOrd for Segment<V> {
+    fn partial_cmp(&self, other: &Segment<V>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<V> Ord for Segment<V> {
+    fn cmp(&self, other: &Segment<V>) -> Ordering {
+        self.error.total_cmp(&other.error)
+    }
+}
+
+pub struct Solver<'a, E: 'a + Evaluate> {
+    evaluator: &'a E,
+    options: QuadOptions,
+    segments: BinaryHeap<Segment<E::Value>>,
+    area: E::Value,
+    error: f64,
+}
+
+impl<'a, E: Evaluate> Solver<'a, E> {
+    pub fn new(evaluator: &'a E, a: f64, b: f64, options: QuadOptions) -> Solver<'a, E> {
+        let estimate = evaluator.estimate(options.rule, a, b);
+        let mut segments = BinaryHeap::new();
+        segments.push(Segment { a, b, area: estimate.area, error: estimate.error, depth: 0 });
+
+        Solver { evaluator, options, segments, area: estimate.area, error: estimate.error }
+    }
+
+    /// Bisects until the tolerance is met or refining has to stop, and
+    /// returns the best estimate, its error and why refining stopped.
+    pub fn run(&mut self) -> (E::Value, f64, Termination) {
+        let tolerance = self.options.tolerance;
+        if !self.area.is_finite() {
+            return (self.area, self.error, Termination::Divergent);
+        }
+        if self.error <= tolerance.target(self.area.norm()) {
+            return (self.area, self.error, Termination::Converged);
+        }
+
+        let mut extrapolation = Extrapolation::new();
+        extrapolation.push(self.area);
+        let mut best = (self.area, f64::INFINITY);
+
+        // Segments at least `small_depth` deep count as small. Between
+        // extrapolations only the large ones are bisected, until their
+        // combined error `large_error` drops below `extrapolation_target`.
+        let mut small_depth = 2;
+        let mut large_error = self.error;
+        let mut extrapolation_target = tolerance.target(self.area.norm());
+        let mut refining_large = false;
+        let mut can_extrapolate = true;
+
+        let mut stalled = 0;
+        let mut futile_extrapolations = 0;
+
+        // How much the estimate moved between the last two extrapolations,
+        // and how many times in a row it has moved as far or further in the
+        // same direction.
+        let mut last_step = E::Value::zero();
+        let mut unsettled = 0;
+        let mut last_term = self.area;
+        let mut termination = Termination::SubdivisionLimit;
+
+        while self.segments.len() < self.options.max_subdivisions {
+            let limit = if refining_large { Some(small_depth) } else { None };
+            let parent = match self.pop_worst(limit) {
+                Some(parent) => parent,
+                None => {
+                    // Nothing large is left, so extrapolate straight away.
+                    refining_large = false;
+                    small_depth += 1;
+                    large_error = self.error;
+                    continue;
+                }
+            };
+            let (left, right) = self.bisect(parent);
+
+            let children_area = left.area + right.area;
+            let children_error = left.error + right.error;
+            self.area += children_area - parent.area;
+            self.error += children_error - parent.error;
+
+            if children_error >= 0.99*parent.error
+                && (parent.area - children_area).norm() <= 1e-5*children_area.norm() {
+                stalled += 1;
+            }
+
+            let too_small = !(parent.a < left.b && left.b < parent.b);
+            self.segments.push(left);
+            self.segments.push(right);
+
+            if !(self.area.is_finite() && self.error.is_finite()) {
+                termination = Termination::Divergent;
+                break;
+            }
+
+            let target = tolerance.target(self.area.norm());
+            if self.error <= target {
+                termination = Termination::Converged;
+                break;
+            }
+            if stalled >= MAX_STALLED {
+                termination = Termination::RoundoffError;
+                break;
+            }
+            if too_small {
+                termination = Termination::IntervalTooSmall;
+                break;
+            }
+            if !can_extrapolate {
+                continue;
+            }
+
+            large_error -= parent.error;
+            if left.depth < small_depth {
+                large_error += children_error;
+            }
+
+            if !refining_large {
+                // Keep bisecting while the worst segment is still large.
+                if self.peek_depth() < small_depth {
+                    continue;
+                }
+                refining_large = true;
+            }
+
+            if large_error > extrapolation_target && self.has_large(small_depth) {
+                continue;
+            }
+
+            let (limit, limit_error) = extrapolation.push(self.area);
+            futile_extrapolations += 1;
+
+            // A convergent sequence of estimates moves by ever smaller steps,
+            // while one growing like a logarithm moves by steps that do not
+            // shrink.
+            let step = self.area - last_term;
+            if same_direction(step, last_step) && step.norm() >= 0.99*last_step.norm() {
+                unsettled += 1;
+            } else {
+                unsettled = 0;
+            }
+            last_step = step;
+            last_term = self.area;
+
+            if futile_extrapolations > MAX_FUTILE_EXTRAPOLATIONS
+                && (best.1 < 1e-3*self.error || unsettled >= MAX_FUTILE_EXTRAPOLATIONS) {
+                termination = Termination::Divergent;
+                break;
+            }
+            if limit_error < best.1 {
+                futile_extrapolations = 0;
+                best = (limit, limit_error);
+                extrapolation_target = tolerance.target(limit.norm());
+                if best.1 <= extrapolation_target {
+                    termination = Termination::Converged;
+                    break;
+                }
+            }
+            if extrapolation.len() == 1 {
+                can_extrapolate = false;
+            }
+
+            refining_large = false;
+            small_depth += 1;
+            large_error = self.error;
+        }
+
+        // Sum afresh to shed the rounding error of the running totals.
+        self.area = self.segments.iter().fold(E::Value::zero(), |area, s| area + s.area);
+        self.error = self.segments.iter().map(|s| s.error).sum();
+
+        if best.1 < self.error {
+            (best.0, best.1, termination)
+        } else {
+            (self.area, self.error, termination)
+        }
+    }
+
+    /// Removes the segment with the largest error, or with `limit`, the
+    /// one with the largest error among those shallower than `limit`.
+    fn pop_worst(&mut self, limit: Option<usize>) -> Option<Segment<E::Value>> {
+        let limit = match limit {
+            Some(limit) => limit,
+            None => return self.segments.pop(),
+        };
+
+        let mut skipped = Vec::new();
+        let mut found = None;
+        while let Some(segment) = self.segments.pop() {
+            if segment.depth < limit {
+                found = Some(segment);
+                break;
+            }
+            skipped.push(segment);
+        }
+        self.segments.extend(skipped);
+
+        found
+    }
+
+    fn peek_depth(&self) -> usize {
+        self.segments.peek().map_or(0, |s| s.depth)
+    }
+
+    fn has_large(&self, small_depth: usize) -> bool {
+        self.segments.iter().any(|s| s.depth < small_depth)
+    }
+
+    fn bisect(&self, parent: Segment<E::Value>) -> (Segment<E::Value>, Segment<E::Value>) {
+        let mid = 0.5*(parent.a + parent.b);
+        let depth = parent.depth + 1;
+        let rule = self.options.rule;
+
+        let (left, right) = self.evaluator.halves(rule, parent.a, mid, parent.b);
+
+        (
+            Segment { a: parent.a, b: mid, area: left.area, error: left.error, depth },
+            Segment { a: mid, b: parent.b, area: right.area, error: right.error, depth },
+        )
+    }
+}
+
+/// Whether `a` and `b` point the same way, that is whether their inner
+/// product, a quarter of `|a + b|^2 - |a - b|^2`, is positive.
+fn same_direction<V: Ordinate<f64>>(a: V, b: V) -> bool {
+    (a + b).norm() > (a - b).norm()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quad_smooth_single_interval() {
+        let expected = 1f64.exp() - 1.0;
+        let result = quad(f64::exp, 0.0, 1.0).unwrap();
+
+        assert!((expected - result.value).abs() < 1e-15);
+        assert_eq!(21, result.evaluations);
+        assert_eq!(Termination::Converged, result.termination);
+    }
+
+    #[test]
+    fn test_quad_gk15() {
+        let options = QuadOptions { rule: KronrodRule::Gk15, ..QuadOptions::default() };
+        let result = quad_with(f64::exp, 0.0, 1.0, options).unwrap();
+
+        assert_eq!(15, result.evaluations);
+        assert!(result.converged());
+    }
+
+    #[test]
+    fn test_quad_reversed_bounds() {
+        let expected = -2.0;
+        let result = quad(f64::sin, std::f64::consts::PI, 0.0).unwrap();
+
+        assert!((expected - result.value).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_quad_inverse_sqrt_singularity() {
+        let expected = 2.0;
+        let result = quad(|x: f64| 1.0/x.sqrt(), 0.0, 1.0).unwrap();
+
+        assert!(result.converged());
+        assert!((expected - result.value).abs() < 1e-10);
+        assert!(result.evaluations < 1000);
+    }
+
+    #[test]
+    fn test_quad_log_singularity() {
+        let expected = -4.0;
+        let result = quad(|x: f64| x.ln()/x.sqrt(), 0.0, 1.0).unwrap();
+
+        assert!(result.converged());
+        assert!((expected - result.value).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_quad_sharp_peak() {
+        let f = |x: f64| 1.0/(1e-6 + (x - 0.3)*(x - 0.3));
+        let expected = 1e3*((0.7f64/1e-3).atan() + (0.3f64/1e-3).atan());
+        let result = quad(f, 0.0, 1.0).unwrap();
+
+        assert!(result.converged());
+        assert!(((expected - result.value)/expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_quad_subdivision_limit() {
+        let options = QuadOptions {
+            tolerance: Tolerance::absolute(1e-14),
+            max_subdivisions: 3,
+            ..QuadOptions::default()
+        };
+        let result = quad_with(|x: f64| (100.0*x).sin()*x.sqrt(), 0.0, 10.0, options).unwrap();
+
+        assert_eq!(Termination::SubdivisionLimit, result.termination);
+        assert!(!result.converged());
+    }
+
+    #[test]
+    fn test_quad_upper_infinite() {
+        let expected = 0.5;
+        let result = quad(|x: f64| (-2.0*x).exp(), 0.0, f64::INFINITY).unwrap();
+
+        assert!(result.converged());
+        assert!((expected - result.value).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_quad_lower_infinite() {
+        // The integral of 1/(1 + x^2) over (-inf, 1]
+        let expected = 0.75*std::f64::consts::PI;
+        let result = quad(|x: f64| 1.0/(1.0 + x*x), f64::NEG_INFINITY, 1.0).unwrap();
+
+        assert!(result.converged());
+        assert!((expected - result.value).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_quad_whole_line_normal_density() {
+        let density = |x: f64| (-0.5*x*x).exp()/(2.0*std::f64::consts::PI).sqrt();
+        let result = quad(density, f64::NEG_INFINITY, f64::INFINITY).unwrap();
+
+        assert!(result.converged());
+        assert!((1.0 - result.value).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_quad_reversed_infinite() {
+        let expected = -1.0;
+        let result = quad(|x: f64| (-x).exp(), f64::INFINITY, 0.0).unwrap();
+
+        assert!((expected - result.value).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_quad_empty_infinite() {
+        let result = quad(|x: f64| x, f64::INFINITY, f64::INFINITY).unwrap();
+
+        assert_eq!(0.0, result.value);
+        assert_eq!(0, result.evaluations);
+    }
+
+    #[test]
+    fn test_quad_logarithmic_divergence() {
+        let result = quad(|x: f64| 1.0/x, 0.0, 1.0).unwrap();
+
+        assert_eq!(Termination::Divergent, result.termination);
+        assert!(!result.converged());
+    }
+
+    #[test]
+    fn test_quad_overflow_is_not_converged() {
+        let result = quad(|_| 1e300, 0.0, 1e10).unwrap();
+
+        assert_eq!(Termination::Divergent, result.termination);
+        assert!(result.value.is_infinite());
+    }
+
+    #[test]
+    fn test_quad_roundoff() {
+        // Noise far above the tolerance keeps every bisection from reducing
+        // the error, while leaving the area almost unchanged.
+        let f = |x: f64| 1.0 + 1e-9*((1e8*x).sin()*1e4).fract();
+        let options = QuadOptions { tolerance: Tolerance::absolute(1e-15), ..QuadOptions::default() };
+        let result = quad_with(f, 0.0, 1.0, options).unwrap();
+
+        assert_eq!(Termination::RoundoffError, result.termination);
+        assert!((1.0 - result.value).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_quad_non_finite_integrand() {
+        let expected = Err(IntegrationError::NonFiniteIntegrand);
+
+        assert_eq!(expected, quad(|_| f64::NAN, 0.0, 1.0));
+        assert_eq!(expected, quad(|x: f64| (-x).exp(), -1e300, 1e300));
+        assert_eq!(expected, quad(|x: f64| if x > 0.7 { f64::INFINITY } else { x }, 0.0, 1.0));
+    }
+
+    #[test]
+    fn test_quad_nan_bound() {
+        let expected = Err(IntegrationError::NonFiniteBounds);
+        let actual = quad(f64::sin, 0.0, f64::NAN);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_quad_zero_subdivisions() {
+        let options = QuadOptions { max_subdivisions: 0, ..QuadOptions::default() };
+
+        let expected = Err(IntegrationError::InvalidParameter { name: "max_subdivisions" });
+        let actual = quad_with(f64::sin, 0.0, 1.0, options);
+
+        assert_eq!(expected, actual);
+    }
+}
@@ -0,0 +1,116 @@
+//! Types shared by the adaptive rules.
+
+use super::IntegrationError;
+
+/// How accurate the result of an adaptive rule must be.
+///
+/// An estimate is accepted once its error estimate is no larger than
+/// `absolute` or `relative` times the magnitude of the estimate, whichever
+/// is larger.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tolerance {
+    pub absolute: f64,
+    pub relative: f64,
+}
+
+impl Tolerance {
+    /// A tolerance on the absolute error only.
+    pub fn absolute(absolute: f64) -> Tolerance {
+        Tolerance { absolute, relative: 0.0 }
+    }
+
+    /// A tolerance on the relative error only.
+    pub fn relative(relative: f64) -> Tolerance {
+        Tolerance { absolute: 0.0, relative }
+    }
+
+    /// The largest error allowed for an estimate of `value`.
+    pub fn target(&self, value: f64) -> f64 {
+        self.absolute.max(self.relative*value.abs())
+    }
+
+    /// Checks that both tolerances are non-negative and that at least one
+    /// of them is positive.
+    pub fn check(&self) -> Result<(), IntegrationError> {
+        let valid = self.absolute >= 0.0 && self.relative >= 0.0
+            && (self.absolute > 0.0 || self.relative > 0.0);
+        if !valid {
+            return Err(IntegrationError::InvalidTolerance);
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for Tolerance {
+    fn default() -> Tolerance {
+        Tolerance { absolute: 1e-10, relative: 1e-10 }
+    }
+}
+
+/// Why an adaptive rule stopped refining.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Termination {
+    /// The error estimate met the tolerance.
+    Converged,
+
+    /// The rule ran out of subdivisions before meeting the tolerance.
+    SubdivisionLimit,
+
+    /// A subinterval became too small to split in floating point.
+    IntervalTooSmall,
+
+    /// Splitting stopped reducing the error, so rounding error dominates.
+    RoundoffError,
+
+    /// The estimates did not settle; the integral may be divergent or
+    /// converge very slowly.
+    Divergent,
+}
+
+/// The outcome of an adaptive rule.
+///
+/// The value is an `f64` except for complex integrands, where it is a
+/// `Complex<f64>` and the error bounds its magnitude.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuadratureResult<V = f64> {
+    /// The estimate of the integral.
+    pub value: V,
+
+    /// An estimate of the absolute error in `value`.
+    pub error: f64,
+
+    /// The number of times the integrand was evaluated.
+    pub evaluations: usize,
+
+    /// Why the rule stopped refining.
+    pub termination: Termination,
+}
+
+impl<V> QuadratureResult<V> {
+    /// Whether the error estimate met the tolerance.
+    pub fn converged(&self) -> bool {
+        self.termination == Termination::Converged
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tolerance_target() {
+        let tolerance = Tolerance { absolute: 1e-6, relative: 1e-3 };
+
+        assert_eq!(1e-6, tolerance.target(1e-4));
+        assert_eq!(1e-2, tolerance.target(-10.0));
+    }
+
+    #[test]
+    fn test_tolerance_check() {
+        assert_eq!(Ok(()), Tolerance::absolute(1e-8).check());
+        assert_eq!(Err(IntegrationError::InvalidTolerance), Tolerance::absolute(0.0).check());
+        assert_eq!(Err(IntegrationError::InvalidTolerance), Tolerance::relative(f64::NAN).check());
+        assert_eq!(Err(IntegrationError::InvalidTolerance), Tolerance::relative(-1.0).check());
+    }
+}
@@ -0,0 +1,223 @@
+//! Romberg integration.
+
+use sum::Summation;
+
+use super::function::{check_interval, weighted_sum, Counted, Points};
+#[cfg(feature = "parallel")]
+use super::parallel::par_weighted_sum;
+use super::{kernel, IntegrationError, Ordinate, Tolerance};
+
+/// The deepest level `romberg` refines to. Level `k` evaluates the
+/// integrand at `2^(k - 1)` new points, so deeper levels would take longer
+/// than any integral is worth.
+pub const MAX_ROMBERG_LEVEL: usize = 30;
+
+/// The level from which Romberg integration starts testing for
+/// convergence, so that a few coincidentally equal early estimates are not
+/// mistaken for a converged result.
+const MIN_CONVERGENCE_LEVEL: usize = 3;
+
+/// Options for `romberg`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RombergOptions {
+    /// The most times the trapezoid step is halved, at most
+    /// `MAX_ROMBERG_LEVEL`. Level `k` uses `2^k + 1` points, so this bounds
+    /// the number of evaluations.
+    pub max_level: usize,
+
+    /// The accuracy at which to stop refining.
+    pub tolerance: Tolerance,
+}
+
+impl Default for RombergOptions {
+    fn default() -> RombergOptions {
+        RombergOptions { max_level: 20, tolerance: Tolerance::default() }
+    }
+}
+
+/// The outcome of `romberg`.
+///
+/// The value and the tableau hold whatever `Ordinate` the integrand
+/// returns, an `f64` unless it is complex.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RombergResult<Y = f64> {
+    /// The estimate of the integral, from the last diagonal entry of the
+    /// tableau.
+    pub value: Y,
+
+    /// The norm of the difference between the last two diagonal entries of
+    /// the tableau.
+    pub error: f64,
+
+    /// The number of times the integrand was evaluated.
+    pub evaluations: usize,
+
+    /// Whether `error` met the tolerance before `max_level` was reached.
+    pub converged: bool,
+
+    /// The Romberg tableau. Row `k` starts with the trapezoid rule on `2^k`
+    /// slices, followed by `k` successive Richardson extrapolations.
+    pub tableau: Vec<Vec<Y>>,
+}
+
+/// Computes the integral of `f` over `[a, b]` using Romberg integration.
+///
+/// The trapezoid step is halved level by level, reusing the previous
+/// points, and each new trapezoid estimate is improved by Richardson
+/// extrapolation against the row before it. Refining stops once two
+/// successive diagonal entries agree to within the tolerance, from level
+/// 3 onwards, or once `max_level` is reached. `f` may return any
+/// `Ordinate`, such as a `Complex<f64>`, whose `norm` is then compared with
+/// the tolerance.
+///
+/// Fails if either bound is not finite, if the tolerance is invalid or if
+/// `max_level` is 0 or exceeds `MAX_ROMBERG_LEVEL`.
+///
+/// ```
+/// use oxidize::integrate::{romberg, RombergOptions};
+///
+/// let result = romberg(f64::exp, 0.0, 1.0, RombergOptions::default()).unwrap();
+/// assert!(result.converged);
+/// assert!((result.value - (1f64.exp() - 1.0)).abs() < 1e-12);
+/// ```
+pub fn romberg<Y, F>(f: F, a: f64, b: f64, options: RombergOptions) -> Result<RombergResult<Y>, IntegrationError>
+    where Y: Ordinate<f64>, F: Fn(f64) -> Y
+{
+    solve(&f, a, b, options, weighted_sum)
+}
+
+/// Computes an integral as `romberg` does, evaluating the new points of
+/// every level on several threads.
+///
+/// The values are added in the same order as `romberg` adds them, so the
+/// result is the same.
+///
+/// Fails as `romberg` does.
+///
+/// ```
+/// use oxidize::integrate::{par_romberg, romberg, RombergOptions};
+///
+/// let serial = romberg(f64::exp, 0.0, 1.0, RombergOptions::default()).unwrap();
+/// let parallel = par_romberg(f64::exp, 0.0, 1.0, RombergOptions::default()).unwrap();
+/// assert_eq!(serial, parallel);
+/// ```
+#[cfg(feature = "parallel")]
+pub fn par_romberg<Y, F>(f: F, a: f64, b: f64, options: RombergOptions) -> Result<RombergResult<Y>, IntegrationError>
+    where Y: Ordinate<f64> + Send, F: Fn(f64) -> Y + Sync
+{
+    solve(&f, a, b, options, par_weighted_sum)
+}
+
+/// Runs Romberg integration, adding up the midpoints of every level with
+/// `sum`.
+fn solve<Y, F, S>(f: &F, a: f64, b: f64, options: RombergOptions, sum: S) -> Result<RombergResult<Y>, IntegrationError>
+    where Y: Ordinate<f64>, F: Fn(f64) -> Y, S: Fn(&Counted<F>, usize, Points) -> Y
+{
+    check_interval(a, b)?;
+    options.tolerance.check()?;
+    if options.max_level < 1 || options.max_level > MAX_ROMBERG_LEVEL {
+        return Err(IntegrationError::InvalidParameter { name: "max_level" });
+    }
+
+    let f = Counted::new(f);
+    let mut h = b - a;
+    let ends = [f.call(a), f.call(b)];
+    let mut tableau = vec![vec![kernel::trapezoid(Summation::Naive, 2, h, |i| ends[i])]];
+    let mut error = f64::INFINITY;
+    let mut converged = false;
+
+    for level in 1..options.max_level + 1 {
+        // Halving the step adds a midpoint to every existing slice.
+        let slices = 1usize << (level - 1);
+        let midpoints = sum(&f, slices, &|i| Some((a + (i as f64 + 0.5)*h, 1.0)));
+        h *= 0.5;
+
+        let previous = &tableau[level - 1];
+        let mut row = Vec::with_capacity(level + 1);
+        row.push(previous[0]*0.5 + midpoints*h);
+
+        let mut factor = 1.0;
+        for j in 1..level + 1 {
+            factor *= 4.0;
+            let extrapolated = row[j - 1] + (row[j - 1] - previous[j - 1])/(factor - 1.0);
+            row.push(extrapolated);
+        }
+
+        error = (row[level] - previous[level - 1]).norm();
+        tableau.push(row);
+
+        if level >= MIN_CONVERGENCE_LEVEL && error <= options.tolerance.target(tableau[level][level].norm()) {
+            converged = true;
+            break;
+        }
+    }
+
+    let last = tableau.len() - 1;
+    Ok(RombergResult {
+        value: tableau[last][last],
+        error,
+        evaluations: f.evaluations(),
+        converged,
+        tableau,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use integrate::trapezoidal_fn;
+
+    #[test]
+    fn test_romberg_first_column_is_trapezoidal() {
+        let options = RombergOptions { max_level: 4, tolerance: Tolerance::absolute(1e-300) };
+        let result = romberg(f64::exp, 0.0, 2.0, options).unwrap();
+
+        for (level, row) in result.tableau.iter().enumerate() {
+            let expected = trapezoidal_fn(f64::exp, 0.0, 2.0, 1 << level).unwrap();
+            assert!((expected - row[0]).abs() < 1e-14);
+        }
+    }
+
+    #[test]
+    fn test_romberg_tableau_shape() {
+        let options = RombergOptions { max_level: 4, tolerance: Tolerance::absolute(1e-300) };
+        let result = romberg(f64::exp, 0.0, 2.0, options).unwrap();
+
+        let lengths: Vec<usize> = result.tableau.iter().map(|row| row.len()).collect();
+
+        assert_eq!(vec![1, 2, 3, 4, 5], lengths);
+        assert_eq!(17, result.evaluations);
+        assert!(!result.converged);
+    }
+
+    #[test]
+    fn test_romberg_sin() {
+        let expected = 2.0;
+        let result = romberg(f64::sin, 0.0, std::f64::consts::PI, RombergOptions::default()).unwrap();
+
+        assert!(result.converged);
+        assert!((expected - result.value).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_romberg_max_level_too_deep() {
+        // 1 << 64 would overflow, and far shallower levels already take
+        // billions of evaluations.
+        let expected = Err(IntegrationError::InvalidParameter { name: "max_level" });
+
+        for &max_level in &[MAX_ROMBERG_LEVEL + 1, 65, usize::MAX] {
+            let options = RombergOptions { max_level, ..RombergOptions::default() };
+            assert_eq!(expected, romberg(f64::sin, 0.0, 1.0, options));
+        }
+    }
+
+    #[test]
+    fn test_romberg_zero_max_level() {
+        let options = RombergOptions { max_level: 0, ..RombergOptions::default() };
+
+        let expected = Err(IntegrationError::InvalidParameter { name: "max_level" });
+        let actual = romberg(f64::sin, 0.0, 1.0, options);
+
+        assert_eq!(expected, actual);
+    }
+}
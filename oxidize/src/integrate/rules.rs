@@ -0,0 +1,171 @@
+//! The sample-based rules, written once over any layout of `Samples`.
+//!
+//! The public rules in this module's parent document the assumptions and
+//! failures; these take the data through `Samples` so that tuple slices,
+//! separate columns and strided views share the same arithmetic.
+
+use sum::Summation;
+
+use super::cumulative::second_interval;
+use super::samples::Samples;
+use super::{kernel, validate, Float, IntegrationError, Ordinate};
+
+pub fn trapezoidal<T: Float, Y: Ordinate<T>, S: Samples<T, Y> + ?Sized>(data: &S, summation: Summation) -> Y {
+    if data.len() <= 1 {
+        return Y::zero();
+    }
+
+    let h = data.x(1) - data.x(0);
+    kernel::trapezoid(summation, data.len(), h, |i| data.y(i))
+}
+
+pub fn trapezoidal_nonuniform<T: Float, Y: Ordinate<T>, S: Samples<T, Y> + ?Sized>(data: &S, summation: Summation) -> Y {
+    let half = T::from_f64(0.5);
+    summation.sum(Y::zero(), data.len().saturating_sub(1), |k| {
+        (data.y(k) + data.y(k + 1))*(half*(data.x(k + 1) - data.x(k)))
+    })
+}
+
+pub fn simpson<T: Float, Y: Ordinate<T>, S: Samples<T, Y> + ?Sized>(data: &S, summation: Summation) -> Y {
+    if data.len() <= 2 {
+        return Y::zero();
+    }
+
+    if data.len().is_multiple_of(2) {
+        return Y::zero();
+    }
+
+    let h = data.x(1) - data.x(0);
+    kernel::simpson(summation, data.len(), h, |i| data.y(i))
+}
+
+pub fn simpson38<T: Float, Y: Ordinate<T>, S: Samples<T, Y> + ?Sized>(data: &S, summation: Summation) -> Y {
+    if data.len() <= 3 {
+        return Y::zero();
+    }
+
+    if !(data.len() - 1).is_multiple_of(3) {
+        return Y::zero();
+    }
+
+    let h = data.x(1) - data.x(0);
+    kernel::simpson38(summation, data.len(), h, |i| data.y(i))
+}
+
+pub fn simpson_auto<T: Float, Y: Ordinate<T>, S: Samples<T, Y> + ?Sized>(data: &S, summation: Summation) -> Y {
+    if data.len() <= 2 {
+        return Y::zero();
+    }
+
+    let h = data.x(1) - data.x(0);
+    kernel::simpson_auto(summation, data.len(), h, |i| data.y(i))
+}
+
+pub fn simpson_nonuniform<T: Float, Y: Ordinate<T>, S: Samples<T, Y> + ?Sized>(data: &S, summation: Summation) -> Y {
+    if data.len() <= 2 {
+        return Y::zero();
+    }
+
+    if data.len().is_multiple_of(2) {
+        return Y::zero();
+    }
+
+    panels(data, (data.len() - 1)/2, summation)
+}
+
+pub fn simpson_auto_nonuniform<T: Float, Y: Ordinate<T>, S: Samples<T, Y> + ?Sized>(data: &S, summation: Summation) -> Y {
+    let n = data.len();
+    if n <= 2 || !n.is_multiple_of(2) {
+        return simpson_nonuniform(data, summation);
+    }
+
+    // The last interval is left over from the pairs and takes the
+    // quadratic through the last three points.
+    let point = |i| (data.x(i), data.y(i));
+    panels(data, (n - 2)/2, summation) + second_interval(&[point(n - 3), point(n - 2), point(n - 1)])
+}
+
+/// Adds up `simpson_panel` over the first `pairs` pairs of intervals.
+fn panels<T: Float, Y: Ordinate<T>, S: Samples<T, Y> + ?Sized>(data: &S, pairs: usize, summation: Summation) -> Y {
+    summation.sum(Y::zero(), pairs, |k| {
+        let i = 2*k;
+        simpson_panel(
+            (data.x(i), data.y(i)),
+            (data.x(i + 1), data.y(i + 1)),
+            (data.x(i + 2), data.y(i + 2)),
+        )
+    })
+}
+
+/// Integrates the quadratic through three points, which may be unevenly
+/// spaced, from the first to the last.
+pub fn simpson_panel<T: Float, Y: Ordinate<T>>(p0: (T, Y), p1: (T, Y), p2: (T, Y)) -> Y {
+    let two = T::from_f64(2.0);
+    let six = T::from_f64(6.0);
+
+    let h0 = p1.0 - p0.0;
+    let h1 = p2.0 - p1.0;
+    let sum = h0 + h1;
+
+    let mut subres = Y::zero();
+    subres += p0.1*(two - h1/h0);
+    subres += p1.1*(sum*sum/(h0*h1));
+    subres += p2.1*(two - h0/h1);
+
+    subres*(sum/six)
+}
+
+pub fn try_trapezoidal<T: Float, Y: Ordinate<T>, S: Samples<T, Y> + ?Sized>(data: &S) -> Result<Y, IntegrationError> {
+    validate::check_points(data, 2)?;
+    validate::check_uniform(data, validate::spacing_tolerance())?;
+
+    Ok(trapezoidal(data, Summation::Naive))
+}
+
+pub fn try_trapezoidal_nonuniform<T: Float, Y: Ordinate<T>, S: Samples<T, Y> + ?Sized>(data: &S) -> Result<Y, IntegrationError> {
+    validate::check_points(data, 2)?;
+
+    Ok(trapezoidal_nonuniform(data, Summation::Naive))
+}
+
+pub fn try_simpson<T: Float, Y: Ordinate<T>, S: Samples<T, Y> + ?Sized>(data: &S) -> Result<Y, IntegrationError> {
+    validate::check_points(data, 3)?;
+    if data.len().is_multiple_of(2) {
+        return Err(IntegrationError::WrongParity { actual: data.len() });
+    }
+    validate::check_uniform(data, validate::spacing_tolerance())?;
+
+    Ok(simpson(data, Summation::Naive))
+}
+
+pub fn try_simpson38<T: Float, Y: Ordinate<T>, S: Samples<T, Y> + ?Sized>(data: &S) -> Result<Y, IntegrationError> {
+    validate::check_points(data, 4)?;
+    if !(data.len() - 1).is_multiple_of(3) {
+        return Err(IntegrationError::WrongIntervalCount { multiple: 3, actual: data.len() - 1 });
+    }
+    validate::check_uniform(data, validate::spacing_tolerance())?;
+
+    Ok(simpson38(data, Summation::Naive))
+}
+
+pub fn try_simpson_auto<T: Float, Y: Ordinate<T>, S: Samples<T, Y> + ?Sized>(data: &S) -> Result<Y, IntegrationError> {
+    validate::check_points(data, 3)?;
+    validate::check_uniform(data, validate::spacing_tolerance())?;
+
+    Ok(simpson_auto(data, Summation::Naive))
+}
+
+pub fn try_simpson_auto_nonuniform<T: Float, Y: Ordinate<T>, S: Samples<T, Y> + ?Sized>(data: &S) -> Result<Y, IntegrationError> {
+    validate::check_points(data, 3)?;
+
+    Ok(simpson_auto_nonuniform(data, Summation::Naive))
+}
+
+pub fn try_simpson_nonuniform<T: Float, Y: Ordinate<T>, S: Samples<T, Y> + ?Sized>(data: &S) -> Result<Y, IntegrationError> {
+    validate::check_points(data, 3)?;
+    if data.len().is_multiple_of(2) {
+        return Err(IntegrationError::WrongParity { actual: data.len() });
+    }
+
+    Ok(simpson_nonuniform(data, Summation::Naive))
+}
@@ -0,0 +1,97 @@
+//! The layouts sampled data can come in, behind one interface, so each
+//! sample-based rule is written once.
+
+use super::{Float, Ordinate, Strided};
+
+/// Sampled data, indexed from 0 to `len() - 1`.
+pub trait Samples<T: Float, Y: Ordinate<T>> {
+    fn len(&self) -> usize;
+
+    fn x(&self, i: usize) -> T;
+
+    fn y(&self, i: usize) -> Y;
+
+    /// Whether the abscissae are evenly spaced by construction, so their
+    /// spacing need not be checked.
+    fn evenly_spaced(&self) -> bool {
+        false
+    }
+}
+
+impl<T: Float, Y: Ordinate<T>> Samples<T, Y> for [(T, Y)] {
+    fn len(&self) -> usize {
+        <[(T, Y)]>::len(self)
+    }
+
+    fn x(&self, i: usize) -> T {
+        self[i].0
+    }
+
+    fn y(&self, i: usize) -> Y {
+        self[i].1
+    }
+}
+
+/// Abscissae and ordinates in separate slices, as long as the shorter.
+pub struct Columns<'a, T: 'a, Y: 'a> {
+    pub xs: &'a [T],
+    pub ys: &'a [Y],
+}
+
+impl<'a, T: Float, Y: Ordinate<T>> Samples<T, Y> for Columns<'a, T, Y> {
+    fn len(&self) -> usize {
+        self.xs.len().min(self.ys.len())
+    }
+
+    fn x(&self, i: usize) -> T {
+        self.xs[i]
+    }
+
+    fn y(&self, i: usize) -> Y {
+        self.ys[i]
+    }
+}
+
+/// Ordinates sampled every `dx`, starting at 0.
+pub struct Uniform<'a, T, Y: 'a> {
+    pub dx: T,
+    pub ys: &'a [Y],
+}
+
+impl<'a, T: Float, Y: Ordinate<T>> Samples<T, Y> for Uniform<'a, T, Y> {
+    fn len(&self) -> usize {
+        self.ys.len()
+    }
+
+    fn x(&self, i: usize) -> T {
+        T::from_usize(i)*self.dx
+    }
+
+    fn y(&self, i: usize) -> Y {
+        self.ys[i]
+    }
+
+    fn evenly_spaced(&self) -> bool {
+        true
+    }
+}
+
+/// Abscissae and ordinates in strided views, as long as the shorter.
+pub struct StridedColumns<'a, T: 'a, Y: 'a> {
+    pub xs: Strided<'a, T>,
+    pub ys: Strided<'a, Y>,
+}
+
+impl<'a, T: Float, Y: Ordinate<T>> Samples<T, Y> for StridedColumns<'a, T, Y> {
+    fn len(&self) -> usize {
+        self.xs.len().min(self.ys.len())
+    }
+
+    fn x(&self, i: usize) -> T {
+        self.xs.get(i)
+    }
+
+    fn y(&self, i: usize) -> Y {
+        self.ys.get(i)
+    }
+}
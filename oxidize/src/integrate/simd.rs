@@ -0,0 +1,293 @@
+//! Vectorised forms of the `_dx` rules for `f64` ordinates.
+//!
+//! The trapezoid rule and Simpson's rule only need the sums of the
+//! ordinates at even and at odd positions. The kernels here keep `LANES`
+//! running sums, one for every position modulo `LANES`, which the
+//! processor can add up several at a time, and add the lanes of each
+//! parity together at the end. On x86-64 they use AVX when the processor
+//! has it, checked at run time, and SSE2 otherwise; elsewhere a scalar loop
+//! keeps the same lanes. The lanes are added in the same order every time,
+//! so every kernel gives the same result to the last bit.
+//!
+//! Evenly-spaced abscissae are sorted by construction, and finite when
+//! their span is, while a non-finite ordinate makes the result non-finite.
+//! So rather than check every ordinate in a pass of its own, these rules
+//! check them only when the result or the span is not finite, and report
+//! the same error the `try_` rules would.
+
+use super::columns::uniform;
+use super::samples::{Samples, Uniform};
+use super::{validate, IntegrationError};
+
+/// The number of running sums the kernels keep.
+const LANES: usize = 16;
+
+/// Computes an integral as `try_trapezoidal_dx` does, adding up the
+/// ordinates with vector instructions.
+///
+/// Because the ordinates are added in `LANES` interleaved running sums
+/// rather than one after another, the result may differ from
+/// `try_trapezoidal_dx` in the last bits. It is the same on any processor.
+///
+/// Fails as `try_trapezoidal_dx` does.
+///
+/// ```
+/// use oxidize::integrate::{simd_trapezoidal_dx, try_trapezoidal_dx};
+///
+/// let ys: Vec<f64> = (0..1001).map(|i| (i as f64*1e-3).exp()).collect();
+/// let vectorised = simd_trapezoidal_dx(1e-3, &ys).unwrap();
+/// assert!((vectorised - try_trapezoidal_dx(1e-3, &ys).unwrap()).abs() < 1e-12);
+/// ```
+pub fn simd_trapezoidal_dx(dx: f64, ys: &[f64]) -> Result<f64, IntegrationError> {
+    let data = uniform(dx, ys)?;
+    if ys.len() < 2 {
+        return Err(IntegrationError::TooFewPoints { required: 2, actual: ys.len() });
+    }
+
+    let last = ys.len() - 1;
+    let (even, odd) = alternating_sums(&ys[1..last]);
+    let result = ((ys[0] + ys[last])*0.5 + (even + odd))*dx;
+
+    confirm(&data, 2, result)
+}
+
+/// Computes an integral as `try_simpson_dx` does, adding up the ordinates
+/// with vector instructions.
+///
+/// Because the ordinates are added in `LANES` interleaved running sums
+/// rather than one after another, the result may differ from
+/// `try_simpson_dx` in the last bits. It is the same on any processor.
+///
+/// Fails as `try_simpson_dx` does.
+pub fn simd_simpson_dx(dx: f64, ys: &[f64]) -> Result<f64, IntegrationError> {
+    let data = uniform(dx, ys)?;
+    if ys.len() < 3 {
+        return Err(IntegrationError::TooFewPoints { required: 3, actual: ys.len() });
+    }
+    if ys.len().is_multiple_of(2) {
+        validate::check_points(&data, 3)?;
+        return Err(IntegrationError::WrongParity { actual: ys.len() });
+    }
+
+    // The interior starts at position 1, so its even offsets are the odd
+    // positions.
+    let last = ys.len() - 1;
+    let (subres4, subres2) = alternating_sums(&ys[1..last]);
+
+    let mut result = 0.0;
+    result += ys[0];
+    result += ys[last];
+    result += subres4*4.0;
+    result += subres2*2.0;
+
+    confirm(&data, 3, result*(dx/3.0))
+}
+
+/// Returns `result`, unless checking the points, which is only needed when
+/// the result or the span of the abscissae is not finite, finds an error.
+fn confirm(data: &Uniform<f64, f64>, required: usize, result: f64) -> Result<f64, IntegrationError> {
+    if !(result.is_finite() && data.x(data.len() - 1).is_finite()) {
+        validate::check_points(data, required)?;
+    }
+
+    Ok(result)
+}
+
+/// Returns the sums of the values at even and at odd offsets.
+fn alternating_sums(values: &[f64]) -> (f64, f64) {
+    let mut lanes = Kernel::detect().lane_sums(values);
+
+    // Halving keeps lanes of the same parity together, ending with the
+    // even sum in lane 0 and the odd sum in lane 1.
+    let mut width = LANES;
+    while width > 2 {
+        width /= 2;
+        for j in 0..width {
+            lanes[j] += lanes[j + width];
+        }
+    }
+
+    let blocks = values.len()/LANES*LANES;
+    let (mut even, mut odd) = (lanes[0], lanes[1]);
+    for (k, &value) in values[blocks..].iter().enumerate() {
+        if k % 2 == 0 {
+            even += value;
+        } else {
+            odd += value;
+        }
+    }
+
+    (even, odd)
+}
+
+/// An instruction set the lane sums can be computed with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kernel {
+    // On x86-64 the scalar kernel only serves to check the others.
+    #[cfg_attr(target_arch = "x86_64", allow(dead_code))]
+    Scalar,
+    #[cfg(target_arch = "x86_64")]
+    Sse2,
+    #[cfg(target_arch = "x86_64")]
+    Avx,
+}
+
+impl Kernel {
+    /// The fastest kernel the processor supports.
+    #[cfg(target_arch = "x86_64")]
+    fn detect() -> Kernel {
+        if is_x86_feature_detected!("avx") {
+            Kernel::Avx
+        } else {
+            Kernel::Sse2
+        }
+    }
+
+    /// The fastest kernel the processor supports.
+    #[cfg(not(target_arch = "x86_64"))]
+    fn detect() -> Kernel {
+        Kernel::Scalar
+    }
+
+    /// Returns the sum of the values at every position modulo `LANES`,
+    /// over the whole blocks of `LANES` values.
+    fn lane_sums(self, values: &[f64]) -> [f64; LANES] {
+        match self {
+            Kernel::Scalar => lane_sums_scalar(values),
+            // SSE2 is part of x86-64, and AVX has been detected.
+            #[cfg(target_arch = "x86_64")]
+            Kernel::Sse2 => unsafe { x86::lane_sums_sse2(values) },
+            #[cfg(target_arch = "x86_64")]
+            Kernel::Avx => unsafe { x86::lane_sums_avx(values) },
+        }
+    }
+}
+
+fn lane_sums_scalar(values: &[f64]) -> [f64; LANES] {
+    let mut lanes = [0.0; LANES];
+    for block in values.chunks_exact(LANES) {
+        for (lane, &value) in lanes.iter_mut().zip(block) {
+            *lane += value;
+        }
+    }
+    lanes
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    use std::arch::x86_64::*;
+
+    use super::LANES;
+
+    /// Safety: the processor must support SSE2, as every x86-64 one does.
+    #[target_feature(enable = "sse2")]
+    pub unsafe fn lane_sums_sse2(values: &[f64]) -> [f64; LANES] {
+        let mut sums = [_mm_setzero_pd(); LANES/2];
+        for block in values.chunks_exact(LANES) {
+            let p = block.as_ptr();
+            for (j, sum) in sums.iter_mut().enumerate() {
+                *sum = _mm_add_pd(*sum, _mm_loadu_pd(p.add(2*j)));
+            }
+        }
+
+        let mut lanes = [0.0; LANES];
+        for (j, &sum) in sums.iter().enumerate() {
+            _mm_storeu_pd(lanes.as_mut_ptr().add(2*j), sum);
+        }
+        lanes
+    }
+
+    /// Safety: the processor must support AVX.
+    #[target_feature(enable = "avx")]
+    pub unsafe fn lane_sums_avx(values: &[f64]) -> [f64; LANES] {
+        let mut sums = [_mm256_setzero_pd(); LANES/4];
+        for block in values.chunks_exact(LANES) {
+            let p = block.as_ptr();
+            for (j, sum) in sums.iter_mut().enumerate() {
+                *sum = _mm256_add_pd(*sum, _mm256_loadu_pd(p.add(4*j)));
+            }
+        }
+
+        let mut lanes = [0.0; LANES];
+        for (j, &sum) in sums.iter().enumerate() {
+            _mm256_storeu_pd(lanes.as_mut_ptr().add(4*j), sum);
+        }
+        lanes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use integrate::{try_simpson_dx, try_trapezoidal_dx};
+
+    fn kernels() -> Vec<Kernel> {
+        let mut kernels = vec![Kernel::Scalar, Kernel::detect()];
+        #[cfg(target_arch = "x86_64")]
+        kernels.push(Kernel::Sse2);
+        kernels
+    }
+
+    #[test]
+    fn test_kernels_agree_bitwise() {
+        let values: Vec<f64> = (0..1000).map(|i| 1.0/(i as f64 + 0.3)).collect();
+
+        for len in (0..70).chain(vec![999, 1000]) {
+            let expected = Kernel::Scalar.lane_sums(&values[..len]);
+            for kernel in kernels() {
+                assert_eq!(expected, kernel.lane_sums(&values[..len]), "{:?}, {}", kernel, len);
+            }
+        }
+    }
+
+    #[test]
+    fn test_alternating_sums() {
+        for len in 0..50 {
+            let values: Vec<f64> = (0..len).map(|i| i as f64).collect();
+            let even: f64 = values.iter().step_by(2).sum();
+            let odd: f64 = values.iter().skip(1).step_by(2).sum();
+
+            assert_eq!((even, odd), alternating_sums(&values), "{}", len);
+        }
+    }
+
+    #[test]
+    fn test_simd_rules_match_scalar() {
+        for &n in &[2, 3, 5, 17, 33, 101, 10_001] {
+            let dx = 1.0/(n - 1) as f64;
+            let ys: Vec<f64> = (0..n).map(|i| (3.0*i as f64*dx).cos()).collect();
+
+            let expected = try_trapezoidal_dx(dx, &ys).unwrap();
+            let actual = simd_trapezoidal_dx(dx, &ys).unwrap();
+            assert!((expected - actual).abs() < 1e-14, "{}", n);
+
+            if n % 2 == 1 {
+                let expected = try_simpson_dx(dx, &ys).unwrap();
+                let actual = simd_simpson_dx(dx, &ys).unwrap();
+                assert!((expected - actual).abs() < 1e-14, "{}", n);
+            }
+        }
+    }
+
+    #[test]
+    fn test_simd_rules_errors_match_scalar() {
+        let mut ys = vec![1.0; 20];
+        ys[7] = f64::NAN;
+        let cases: Vec<(f64, &[f64])> = vec![
+            (0.0, &[1.0, 2.0, 3.0]),
+            (f64::INFINITY, &[1.0, 2.0, 3.0]),
+            (1.0, &[]),
+            (1.0, &[1.0]),
+            (1.0, &[1.0, 2.0]),
+            (1.0, &ys),
+            (1.0, &ys[..19]),
+            (1.0, &[1.0, f64::INFINITY, 1.0]),
+            (1e308, &[1.0, 1.0, 1.0]),
+        ];
+
+        for (dx, ys) in cases {
+            assert_eq!(try_trapezoidal_dx(dx, ys), simd_trapezoidal_dx(dx, ys));
+            assert_eq!(try_simpson_dx(dx, ys), simd_simpson_dx(dx, ys));
+        }
+    }
+}
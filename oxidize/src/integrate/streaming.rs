@@ -0,0 +1,485 @@
+//! Integration of samples that arrive one at a time, from a stream or from
+//! any iterator.
+//!
+//! Only the rules that use the actual width of every interval, the
+//! trapezoid rule and Simpson's rule with the last interval of an even
+//! number taken under the quadratic through the last three samples, have
+//! streaming and iterator forms, with and without a choice of summation.
+//! On evenly spaced samples they give the evenly spaced rules' results to
+//! within rounding, and they need no spacing check, which would otherwise
+//! have to fail only after the whole stream was taken. The 3/8 rule needs
+//! the number of intervals to be a multiple of 3, which a stream only
+//! settles at its end, and the cumulative rules return a value for every
+//! sample rather than one for all of them, so neither has a form here.
+
+use sum::{OnlineSummation, RunningSum};
+
+use super::cumulative::second_interval;
+use super::rules::simpson_panel;
+use super::{Float, IntegrationError, Ordinate};
+
+/// The rule a `StreamingIntegrator` applies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamingRule {
+    /// The trapezoid rule with the actual width of every interval, as
+    /// `trapezoidal_nonuniform` applies it.
+    Trapezoid,
+
+    /// Simpson's rule with the actual widths of every pair of intervals, as
+    /// `simpson_auto_nonuniform` applies it: with an even number of
+    /// samples, the last interval is integrated under the quadratic through
+    /// the last three.
+    Simpson,
+}
+
+/// Integrates samples as they arrive, without keeping them.
+///
+/// Only the last few samples and the integral over the ones before them
+/// are kept, so a stream of any length takes constant memory. The
+/// intervals are added up in the same order as by the batch rules, and
+/// with the same `Summation`, so at any spacing `value` is exactly what
+/// `trapezoidal_nonuniform_with` or `simpson_auto_nonuniform_with` would
+/// return for all the samples so far.
+///
+/// ```
+/// use oxidize::integrate::{trapezoidal_nonuniform, StreamingIntegrator, StreamingRule};
+///
+/// let samples = [(0.0, 1.0), (0.5, 2.0), (2.0, 0.5), (2.5, 3.0)];
+///
+/// let mut integrator = StreamingIntegrator::new(StreamingRule::Trapezoid);
+/// for &(x, y) in &samples {
+///     integrator.push(x, y).unwrap();
+/// }
+/// assert_eq!(trapezoidal_nonuniform(&samples), integrator.value());
+/// ```
+#[derive(Debug, Clone)]
+pub struct StreamingIntegrator<T = f64, Y = f64> {
+    rule: StreamingRule,
+    len: usize,
+
+    /// The integral over the completed intervals, or over the completed
+    /// pairs of intervals for Simpson's rule, with its compensation.
+    total: RunningSum<T, Y>,
+
+    /// The last sample taken.
+    last: Option<(T, Y)>,
+
+    /// For Simpson's rule, the first sample of the open pair of intervals.
+    start: Option<(T, Y)>,
+
+    /// For Simpson's rule, the middle sample of the last completed pair.
+    before: Option<(T, Y)>,
+}
+
+impl<T: Float, Y: Ordinate<T>> StreamingIntegrator<T, Y> {
+    /// Creates an integrator with no samples that applies `rule`, adding
+    /// up the intervals naively.
+    pub fn new(rule: StreamingRule) -> StreamingIntegrator<T, Y> {
+        StreamingIntegrator { rule, len: 0, total: RunningSum::default(), last: None, start: None, before: None }
+    }
+
+    /// Creates an integrator with no samples that applies `rule`, adding
+    /// up the intervals with `summation`.
+    ///
+    /// ```
+    /// use oxidize::integrate::{trapezoidal_nonuniform_with, StreamingIntegrator, StreamingRule};
+    /// use oxidize::sum::{OnlineSummation, Summation};
+    ///
+    /// let samples: Vec<(f64, f64)> = (0..100_001).map(|i| (i as f64*1e-5, 0.1)).collect();
+    ///
+    /// let mut integrator = StreamingIntegrator::with_summation(StreamingRule::Trapezoid, OnlineSummation::Neumaier);
+    /// integrator.extend(samples.iter().cloned()).unwrap();
+    /// assert_eq!(trapezoidal_nonuniform_with(&samples, Summation::Neumaier), integrator.value());
+    /// ```
+    pub fn with_summation(rule: StreamingRule, summation: OnlineSummation) -> StreamingIntegrator<T, Y> {
+        StreamingIntegrator { rule, len: 0, total: RunningSum::new(summation), last: None, start: None, before: None }
+    }
+
+    /// The rule being applied.
+    pub fn rule(&self) -> StreamingRule {
+        self.rule
+    }
+
+    /// The way the intervals are added up.
+    pub fn summation(&self) -> OnlineSummation {
+        self.total.summation()
+    }
+
+    /// The number of samples taken so far.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether no sample has been taken yet.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Takes the next sample.
+    ///
+    /// Fails if either coordinate is not finite or if `x` is not greater
+    /// than the abscissa of the previous sample, reporting the sample's
+    /// position in the stream. A sample that fails is not taken.
+    pub fn push(&mut self, x: T, y: Y) -> Result<(), IntegrationError> {
+        let index = self.len;
+        if !x.is_finite() || !y.is_finite() {
+            return Err(IntegrationError::NonFinite { index });
+        }
+
+        match self.last {
+            Some(last) if x <= last.0 => return Err(IntegrationError::Unsorted { index }),
+            _ => {}
+        }
+
+        let sample = (x, y);
+        match (self.rule, self.last, self.start) {
+            (StreamingRule::Trapezoid, Some(last), _) => {
+                self.total.add((last.1 + y)*(T::from_f64(0.5)*(x - last.0)));
+            }
+            (StreamingRule::Simpson, Some(middle), Some(start)) if index.is_multiple_of(2) => {
+                // This sample closes the open pair of intervals.
+                self.total.add(simpson_panel(start, middle, sample));
+                self.before = Some(middle);
+                self.start = Some(sample);
+            }
+            (StreamingRule::Simpson, None, _) => self.start = Some(sample),
+            _ => {}
+        }
+
+        self.last = Some(sample);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Takes every sample of `samples` in turn.
+    ///
+    /// Fails at the first sample `push` rejects; the samples before it are
+    /// kept and the ones after it are not taken.
+    pub fn extend<I: IntoIterator<Item = (T, Y)>>(&mut self, samples: I) -> Result<(), IntegrationError> {
+        for (x, y) in samples {
+            self.push(x, y)?;
+        }
+        Ok(())
+    }
+
+    /// The integral from the first sample to the last one taken.
+    ///
+    /// Like the batch rules, returns `0.0` until there are enough samples
+    /// to apply the rule: 2 for the trapezoid rule and 3 for Simpson's.
+    pub fn value(&self) -> Y {
+        // The last interval of an even number of samples is added as
+        // `simpson_auto_nonuniform` adds it, after the pairs.
+        let total = self.total.value();
+        match (self.rule, self.before, self.start, self.last) {
+            (StreamingRule::Simpson, Some(before), Some(start), Some(last)) if self.len.is_multiple_of(2) => {
+                total + second_interval(&[before, start, last])
+            }
+            _ => total,
+        }
+    }
+}
+
+/// Computes an integral using the trapezoid rule, with the actual width of
+/// every interval, over samples from any iterator.
+///
+/// The samples are integrated as they are produced, so lazily generated or
+/// filtered data need not be collected first. Unlike `trapezoidal`, which
+/// takes every interval to be as wide as the first, this uses the actual
+/// widths: the result, or the error, is exactly that of
+/// `try_trapezoidal_nonuniform` on the same samples.
+///
+/// Fails if there are fewer than 2 samples, if any coordinate is not finite
+/// or if the abscissae are not strictly increasing, checked in that order
+/// as the slice rules check them.
+///
+/// ```
+/// use oxidize::integrate::trapezoidal_iter;
+///
+/// // Integrate y = x over [0, 1], dropping the samples flagged as bad.
+/// let flagged = [false, false, true, false, false];
+/// let samples = (0..5).map(|i| (i as f64*0.25, i as f64*0.25))
+///     .zip(flagged.iter())
+///     .filter(|&(_, &bad)| !bad)
+///     .map(|(sample, _)| sample);
+/// assert_eq!(Ok(0.5), trapezoidal_iter(samples));
+/// ```
+pub fn trapezoidal_iter<T, Y, I>(samples: I) -> Result<Y, IntegrationError>
+    where T: Float, Y: Ordinate<T>, I: IntoIterator<Item = (T, Y)>
+{
+    integrate_iter(StreamingRule::Trapezoid, OnlineSummation::Naive, 2, samples)
+}
+
+/// Computes an integral as `trapezoidal_iter` does, adding up the intervals
+/// with `summation`.
+pub fn trapezoidal_iter_with<T, Y, I>(samples: I, summation: OnlineSummation) -> Result<Y, IntegrationError>
+    where T: Float, Y: Ordinate<T>, I: IntoIterator<Item = (T, Y)>
+{
+    integrate_iter(StreamingRule::Trapezoid, summation, 2, samples)
+}
+
+/// Computes an integral using Simpson's rule, with the actual widths of
+/// every pair of intervals, over samples from any iterator.
+///
+/// The number of samples need not be known up front: the result, or the
+/// error, is exactly that of `try_simpson_auto_nonuniform` on the same
+/// samples, and so that of `try_simpson_nonuniform` when their number is
+/// odd.
+///
+/// Fails if there are fewer than 3 samples, if any coordinate is not finite
+/// or if the abscissae are not strictly increasing, checked in that order
+/// as the slice rules check them.
+///
+/// ```
+/// use oxidize::integrate::simpson_iter;
+///
+/// // 4 samples of y = x^2 over [0, 3]
+/// let area = simpson_iter((0..4).map(|i| (i as f64, (i*i) as f64))).unwrap();
+/// assert!((area - 9.0).abs() < 1e-14);
+/// ```
+pub fn simpson_iter<T, Y, I>(samples: I) -> Result<Y, IntegrationError>
+    where T: Float, Y: Ordinate<T>, I: IntoIterator<Item = (T, Y)>
+{
+    integrate_iter(StreamingRule::Simpson, OnlineSummation::Naive, 3, samples)
+}
+
+/// Computes an integral as `simpson_iter` does, adding up the pairs of
+/// intervals with `summation`.
+pub fn simpson_iter_with<T, Y, I>(samples: I, summation: OnlineSummation) -> Result<Y, IntegrationError>
+    where T: Float, Y: Ordinate<T>, I: IntoIterator<Item = (T, Y)>
+{
+    integrate_iter(StreamingRule::Simpson, summation, 3, samples)
+}
+
+fn integrate_iter<T, Y, I>(rule: StreamingRule, summation: OnlineSummation, required: usize, samples: I) -> Result<Y, IntegrationError>
+    where T: Float, Y: Ordinate<T>, I: IntoIterator<Item = (T, Y)>
+{
+    let mut integrator = StreamingIntegrator::with_summation(rule, summation);
+    let mut samples = samples.into_iter();
+    let failure = integrator.extend(samples.by_ref()).err();
+
+    // Too few samples take precedence over a bad one, as in `check_points`,
+    // so after a failure the samples are only counted, until there are
+    // enough.
+    let mut len = integrator.len();
+    if failure.is_some() {
+        len += 1 + samples.take(required.saturating_sub(len + 1)).count();
+    }
+    if len < required {
+        return Err(IntegrationError::TooFewPoints { required, actual: len });
+    }
+    if let Some(error) = failure {
+        return Err(error);
+    }
+
+    Ok(integrator.value())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sum::Summation;
+    use integrate::{cumulative_simpson, simpson_auto_nonuniform, simpson_nonuniform, trapezoidal_nonuniform, Complex};
+    use integrate::{simpson_auto_nonuniform_with, trapezoidal_nonuniform_with};
+    use integrate::{try_simpson_auto_nonuniform, try_simpson_nonuniform, try_trapezoidal_nonuniform};
+
+    fn samples() -> Vec<(f64, f64)> {
+        let mut x = 0.0;
+        (0..200).map(|i| {
+            x += 0.01 + 0.005*((i*7 % 11) as f64);
+            (x, (3.0*x).sin() + 0.2*x)
+        }).collect()
+    }
+
+    #[test]
+    fn test_streaming_trapezoid_matches_batch() {
+        let data = samples();
+        let mut integrator = StreamingIntegrator::new(StreamingRule::Trapezoid);
+        assert_eq!(0.0, integrator.value());
+
+        for (i, &(x, y)) in data.iter().enumerate() {
+            integrator.push(x, y).unwrap();
+
+            assert_eq!(trapezoidal_nonuniform(&data[..i + 1]), integrator.value());
+        }
+    }
+
+    #[test]
+    fn test_streaming_simpson_matches_batch() {
+        let data = samples();
+        let mut integrator = StreamingIntegrator::new(StreamingRule::Simpson);
+
+        for (i, &(x, y)) in data.iter().enumerate() {
+            integrator.push(x, y).unwrap();
+
+            let n = i + 1;
+            assert_eq!(simpson_auto_nonuniform(&data[..n]), integrator.value(), "{}", n);
+            if n % 2 == 1 {
+                assert_eq!(simpson_nonuniform(&data[..n]), integrator.value());
+            } else if n > 2 {
+                let expected = *cumulative_simpson(&data[..n]).last().unwrap();
+                assert!((expected - integrator.value()).abs() < 1e-14, "{}", n);
+            }
+        }
+    }
+
+    #[test]
+    fn test_streaming_simpson_even_count_exact_for_quadratics() {
+        let f = |x: f64| 3.0*x*x - x + 2.0;
+        let xs = [0.0, 0.3, 0.5, 1.2, 1.3, 2.0];
+
+        let mut integrator = StreamingIntegrator::new(StreamingRule::Simpson);
+        integrator.extend(xs.iter().map(|&x| (x, f(x)))).unwrap();
+
+        let expected = 2.0*2.0*2.0 - 2.0 + 4.0;
+        assert!((expected - integrator.value()).abs() < 1e-13);
+    }
+
+    #[test]
+    fn test_streaming_summation_matches_batch() {
+        let data = samples();
+
+        for &online in &[OnlineSummation::Naive, OnlineSummation::Kahan, OnlineSummation::Neumaier] {
+            let summation = Summation::from(online);
+            let mut trapezoid = StreamingIntegrator::with_summation(StreamingRule::Trapezoid, online);
+            let mut simpson = StreamingIntegrator::with_summation(StreamingRule::Simpson, online);
+            trapezoid.extend(data.iter().cloned()).unwrap();
+            assert_eq!(trapezoidal_nonuniform_with(&data, summation), trapezoid.value());
+
+            // An odd and then an even number of samples
+            simpson.extend(data[..199].iter().cloned()).unwrap();
+            assert_eq!(online, simpson.summation());
+            assert_eq!(simpson_auto_nonuniform_with(&data[..199], summation), simpson.value());
+            simpson.push(data[199].0, data[199].1).unwrap();
+            assert_eq!(simpson_auto_nonuniform_with(&data, summation), simpson.value());
+        }
+    }
+
+    #[test]
+    fn test_streaming_extend_matches_push() {
+        let data = samples();
+        let mut pushed = StreamingIntegrator::new(StreamingRule::Simpson);
+        let mut extended = StreamingIntegrator::new(StreamingRule::Simpson);
+
+        for chunk in data.chunks(7) {
+            for &(x, y) in chunk {
+                pushed.push(x, y).unwrap();
+            }
+            extended.extend(chunk.iter().cloned()).unwrap();
+
+            assert_eq!(pushed.value(), extended.value());
+        }
+        assert_eq!(data.len(), extended.len());
+    }
+
+    #[test]
+    fn test_streaming_rejects_bad_samples() {
+        let mut integrator = StreamingIntegrator::new(StreamingRule::Trapezoid);
+        integrator.push(0.0, 1.0).unwrap();
+        integrator.push(1.0, 1.0).unwrap();
+
+        assert_eq!(Err(IntegrationError::Unsorted { index: 2 }), integrator.push(1.0, 5.0));
+        assert_eq!(Err(IntegrationError::NonFinite { index: 2 }), integrator.push(2.0, f64::NAN));
+        assert_eq!(2, integrator.len());
+        assert_eq!(1.0, integrator.value());
+
+        let actual = integrator.extend(vec![(2.0, 1.0), (3.0, f64::INFINITY), (4.0, 1.0)]);
+        assert_eq!(Err(IntegrationError::NonFinite { index: 3 }), actual);
+        assert_eq!(2.0, integrator.value());
+    }
+
+    #[test]
+    fn test_streaming_complex() {
+        let data: Vec<(f64, Complex<f64>)> = (0..5).map(|i| {
+            let x = i as f64*0.25;
+            (x, Complex::new(x, 1.0))
+        }).collect();
+
+        let mut integrator = StreamingIntegrator::new(StreamingRule::Simpson);
+        integrator.extend(data.iter().cloned()).unwrap();
+
+        assert_eq!(simpson_nonuniform(&data), integrator.value());
+    }
+
+    #[test]
+    fn test_iter_rules_match_slices() {
+        let data = samples();
+
+        for n in 0..data.len() {
+            let expected = try_trapezoidal_nonuniform(&data[..n]);
+            assert_eq!(expected, trapezoidal_iter(data[..n].iter().cloned()));
+
+            let expected = try_simpson_auto_nonuniform(&data[..n]);
+            assert_eq!(expected, simpson_iter(data[..n].iter().cloned()));
+            if n % 2 == 1 {
+                assert_eq!(try_simpson_nonuniform(&data[..n]), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_iter_rules_with_summation_match_slices() {
+        let data = samples();
+
+        for &online in &[OnlineSummation::Naive, OnlineSummation::Kahan, OnlineSummation::Neumaier] {
+            let summation = Summation::from(online);
+            for &n in &[199, 200] {
+                let expected = trapezoidal_nonuniform_with(&data[..n], summation);
+                assert_eq!(Ok(expected), trapezoidal_iter_with(data[..n].iter().cloned(), online));
+
+                let expected = simpson_auto_nonuniform_with(&data[..n], summation);
+                assert_eq!(Ok(expected), simpson_iter_with(data[..n].iter().cloned(), online));
+            }
+        }
+
+        let expected = Err(IntegrationError::TooFewPoints { required: 3, actual: 2 });
+        assert_eq!(expected, simpson_iter_with(data[..2].iter().cloned(), OnlineSummation::Kahan));
+    }
+
+    #[test]
+    fn test_simpson_iter_even_count() {
+        let data = samples();
+
+        let expected = *cumulative_simpson(&data).last().unwrap();
+        let actual = simpson_iter(data.iter().cloned()).unwrap();
+
+        assert_eq!(simpson_auto_nonuniform(&data), actual);
+        assert!((expected - actual).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_iter_rules_errors_match_slices() {
+        let nan = f64::NAN;
+        let cases: Vec<Vec<(f64, f64)>> = vec![
+            vec![],
+            vec![(nan, 1.0)],
+            vec![(0.0, 1.0), (nan, 1.0)],
+            vec![(1.0, 1.0), (0.0, 1.0)],
+            vec![(nan, 1.0), (0.0, 1.0), (1.0, 1.0)],
+            vec![(0.0, 1.0), (1.0, nan), (0.5, 1.0)],
+            vec![(0.0, 1.0), (1.0, 1.0), (0.5, nan), (2.0, 1.0), (3.0, 1.0)],
+            vec![(0.0, 1.0), (1.0, 1.0), (2.0, f64::INFINITY)],
+        ];
+
+        for data in &cases {
+            let expected = try_trapezoidal_nonuniform(data);
+            assert_eq!(expected, trapezoidal_iter(data.iter().cloned()), "{:?}", data);
+
+            let expected = try_simpson_auto_nonuniform(data);
+            assert_eq!(expected, simpson_iter(data.iter().cloned()), "{:?}", data);
+        }
+    }
+
+    #[test]
+    fn test_iter_rules_errors() {
+        let expected = Err(IntegrationError::TooFewPoints { required: 3, actual: 2 });
+        assert_eq!(expected, simpson_iter(vec![(0.0, 1.0), (1.0, 1.0)]));
+
+        let expected = Err(IntegrationError::Unsorted { index: 2 });
+        let actual = trapezoidal_iter((0..5).map(|i| ((i % 2) as f64, 1.0)));
+        assert_eq!(expected, actual);
+
+        let expected = Err(IntegrationError::NonFinite { index: 1 });
+        let actual = simpson_iter(vec![(0.0, 1.0), (f64::NAN, 1.0), (1.0, 1.0)]);
+        assert_eq!(expected, actual);
+    }
+}
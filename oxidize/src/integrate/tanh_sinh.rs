@@ -0,0 +1,276 @@
+//! Tanh-sinh (double exponential) quadrature.
+
+use std::f64::consts::FRAC_PI_2;
+use std::sync::OnceLock;
+
+use super::function::{check_interval, weighted_sum, Counted, Points};
+#[cfg(feature = "parallel")]
+use super::parallel::par_weighted_sum;
+use super::{IntegrationError, Ordinate, QuadratureResult, Termination, Tolerance};
+
+/// The deepest level for which abscissae are tabulated. Level `k` uses a
+/// step of `2^-k` in the transformed variable.
+pub const MAX_TANH_SINH_LEVEL: usize = 12;
+
+/// Beyond this the transformed nodes are closer to the ends than any
+/// `f64` can represent.
+const MAX_T: f64 = 7.0;
+
+/// Options for `tanh_sinh`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TanhSinhOptions {
+    /// The accuracy at which to stop refining.
+    pub tolerance: Tolerance,
+
+    /// The deepest level to refine to, from 1 to `MAX_TANH_SINH_LEVEL`.
+    pub max_level: usize,
+}
+
+impl Default for TanhSinhOptions {
+    fn default() -> TanhSinhOptions {
+        TanhSinhOptions { tolerance: Tolerance::default(), max_level: 8 }
+    }
+}
+
+/// A node of the rule on `[-1, 1]`, stored as its distance from the
+/// nearest end so that nodes crowding the ends keep full precision.
+#[derive(Debug, Clone, Copy)]
+struct Node {
+    complement: f64,
+    weight: f64,
+}
+
+impl Node {
+    fn at(t: f64) -> Node {
+        // With u = pi/2 sinh(t), x = tanh(u) and 1 - x = 2e/(1 + e) where
+        // e = exp(-2u), which neither overflows nor cancels.
+        let u = FRAC_PI_2*t.sinh();
+        let e = (-2.0*u).exp();
+        let complement = 2.0*e/(1.0 + e);
+        let weight = FRAC_PI_2*t.cosh()*4.0*e/((1.0 + e)*(1.0 + e));
+
+        Node { complement, weight }
+    }
+}
+
+/// Returns the positive nodes added at each level: the whole numbers
+/// from 1 at level 0, and the odd multiples of `2^-k` at level `k`.
+fn tables() -> &'static [Vec<Node>] {
+    static TABLES: OnceLock<Vec<Vec<Node>>> = OnceLock::new();
+
+    TABLES.get_or_init(|| {
+        (0..MAX_TANH_SINH_LEVEL + 1)
+            .map(|level| {
+                let h = 0.5f64.powi(level as i32);
+                let (first, step) = if level == 0 { (1.0, 1.0) } else { (h, 2.0*h) };
+
+                let mut nodes = Vec::new();
+                let mut t = first;
+                while t <= MAX_T {
+                    let node = Node::at(t);
+                    if node.complement <= 0.0 || node.weight <= 0.0 {
+                        break;
+                    }
+                    nodes.push(node);
+                    t += step;
+                }
+                nodes
+            })
+            .collect()
+    })
+}
+
+/// Computes the integral of `f` over `[a, b]` using tanh-sinh quadrature.
+///
+/// The substitution `x = tanh(pi/2 sinh(t))` makes the integrand decay
+/// double exponentially, so the trapezoid rule in `t` converges very fast
+/// even when `f` has integrable singularities at `a` or `b`. `f` is never
+/// evaluated at the ends themselves. Each level halves the step in `t`,
+/// reusing the points of the levels before, and the error estimate is the
+/// change from the previous level, measured with `norm` when `f` returns
+/// another `Ordinate`, such as a `Complex<f64>`.
+///
+/// Nodes within rounding distance of a non-zero end cannot be told apart,
+/// so an integrand singular at such an end, computing something like
+/// `1 - x`, is only accurate to about the square root of machine epsilon.
+/// Shift the integrand so the singularity sits at 0 for full accuracy.
+///
+/// Fails if either bound is not finite, if the tolerance is invalid or if
+/// `max_level` is 0 or exceeds `MAX_TANH_SINH_LEVEL`.
+///
+/// ```
+/// use oxidize::integrate::{tanh_sinh, TanhSinhOptions};
+///
+/// let result = tanh_sinh(|x: f64| 1.0/x.sqrt(), 0.0, 1.0, TanhSinhOptions::default()).unwrap();
+/// assert!((result.value - 2.0).abs() < 1e-12);
+/// ```
+pub fn tanh_sinh<Y, F>(f: F, a: f64, b: f64, options: TanhSinhOptions) -> Result<QuadratureResult<Y>, IntegrationError>
+    where Y: Ordinate<f64>, F: Fn(f64) -> Y
+{
+    solve(&f, a, b, options, weighted_sum)
+}
+
+/// Computes an integral as `tanh_sinh` does, evaluating the new nodes of
+/// every level on several threads.
+///
+/// The values are added in the same order as `tanh_sinh` adds them, so the
+/// result is the same.
+///
+/// Fails as `tanh_sinh` does.
+///
+/// ```
+/// use oxidize::integrate::{par_tanh_sinh, tanh_sinh, TanhSinhOptions};
+///
+/// let f = |x: f64| x.ln()/x.sqrt();
+/// let serial = tanh_sinh(f, 0.0, 1.0, TanhSinhOptions::default()).unwrap();
+/// let parallel = par_tanh_sinh(f, 0.0, 1.0, TanhSinhOptions::default()).unwrap();
+/// assert_eq!(serial, parallel);
+/// ```
+#[cfg(feature = "parallel")]
+pub fn par_tanh_sinh<Y, F>(f: F, a: f64, b: f64, options: TanhSinhOptions) -> Result<QuadratureResult<Y>, IntegrationError>
+    where Y: Ordinate<f64> + Send, F: Fn(f64) -> Y + Sync
+{
+    solve(&f, a, b, options, par_weighted_sum)
+}
+
+/// Runs tanh-sinh quadrature, adding up the nodes of every level with
+/// `sum`.
+fn solve<Y, F, S>(f: &F, a: f64, b: f64, options: TanhSinhOptions, sum: S) -> Result<QuadratureResult<Y>, IntegrationError>
+    where Y: Ordinate<f64>, F: Fn(f64) -> Y, S: Fn(&Counted<F>, usize, Points) -> Y
+{
+    check_interval(a, b)?;
+    options.tolerance.check()?;
+    if options.max_level < 1 || options.max_level > MAX_TANH_SINH_LEVEL {
+        return Err(IntegrationError::InvalidParameter { name: "max_level" });
+    }
+
+    let f = Counted::new(f);
+    let half = 0.5*(b - a);
+    let tables = tables();
+
+    let mut total = f.call(0.5*(a + b))*FRAC_PI_2 + level_sum(&f, a, b, &tables[0], &sum);
+    let mut value = total*half;
+    let mut error = f64::INFINITY;
+    let mut termination = Termination::SubdivisionLimit;
+
+    for (level, nodes) in tables.iter().enumerate().take(options.max_level + 1).skip(1) {
+        total += level_sum(&f, a, b, nodes, &sum);
+        let next = total*half*0.5f64.powi(level as i32);
+
+        error = (next - value).norm();
+        value = next;
+
+        if error <= options.tolerance.target(value.norm()) {
+            termination = Termination::Converged;
+            break;
+        }
+    }
+
+    Ok(QuadratureResult { value, error, evaluations: f.evaluations(), termination })
+}
+
+/// Sums the weighted integrand over a level's nodes and their mirror
+/// images with `sum`, skipping any node that rounds onto an end of
+/// `[a, b]`.
+fn level_sum<Y, F, S>(f: &Counted<F>, a: f64, b: f64, nodes: &[Node], sum: &S) -> Y
+    where Y: Ordinate<f64>, F: Fn(f64) -> Y, S: Fn(&Counted<F>, usize, Points) -> Y
+{
+    let half = 0.5*(b - a);
+
+    // Every node gives two points, the one near a and then the one near b.
+    sum(f, 2*nodes.len(), &|i| {
+        let node = nodes[i/2];
+        let offset = half*node.complement;
+
+        if i % 2 == 0 {
+            let left = a + offset;
+            if left != a { Some((left, node.weight)) } else { None }
+        } else {
+            let right = b - offset;
+            if right != b { Some((right, node.weight)) } else { None }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tables_weights_sum_to_two() {
+        // The trapezoid rule in t integrates the weight function, whose
+        // integral over the real line is 2.
+        let tables = tables();
+        let mut sum = FRAC_PI_2;
+        for nodes in &tables[..5] {
+            sum += 2.0*nodes.iter().map(|n| n.weight).sum::<f64>();
+        }
+
+        assert!((sum/16.0 - 2.0).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_tanh_sinh_smooth() {
+        let expected = 1f64.exp() - 1.0;
+        let result = tanh_sinh(f64::exp, 0.0, 1.0, TanhSinhOptions::default()).unwrap();
+
+        assert!(result.converged());
+        assert!((expected - result.value).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_tanh_sinh_inverse_sqrt() {
+        let expected = 2.0;
+        let result = tanh_sinh(|x: f64| 1.0/x.sqrt(), 0.0, 1.0, TanhSinhOptions::default()).unwrap();
+
+        assert!(result.converged());
+        assert!((expected - result.value).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_tanh_sinh_both_ends_singular() {
+        // The integral of 1/sqrt(1 - x^2) over [-1, 1] is pi. Rounding in
+        // 1 - x and 1 + x near the ends limits the accuracy.
+        let f = |x: f64| 1.0/((1.0 - x)*(1.0 + x)).sqrt();
+        let result = tanh_sinh(f, -1.0, 1.0, TanhSinhOptions::default()).unwrap();
+
+        assert!((std::f64::consts::PI - result.value).abs() < 1e-7);
+    }
+
+    #[test]
+    fn test_tanh_sinh_strong_singularity() {
+        let expected = 10.0;
+        let result = tanh_sinh(|x: f64| x.powf(-0.9), 0.0, 1.0, TanhSinhOptions::default()).unwrap();
+
+        assert!((expected - result.value).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_tanh_sinh_log() {
+        let expected = -1.0;
+        let result = tanh_sinh(f64::ln, 0.0, 1.0, TanhSinhOptions::default()).unwrap();
+
+        assert!(result.converged());
+        assert!((expected - result.value).abs() < 1e-13);
+    }
+
+    #[test]
+    fn test_tanh_sinh_max_level_too_deep() {
+        let options = TanhSinhOptions { max_level: MAX_TANH_SINH_LEVEL + 1, ..TanhSinhOptions::default() };
+
+        let expected = Err(IntegrationError::InvalidParameter { name: "max_level" });
+        let actual = tanh_sinh(f64::exp, 0.0, 1.0, options);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_tanh_sinh_zero_max_level() {
+        let options = TanhSinhOptions { max_level: 0, ..TanhSinhOptions::default() };
+
+        let expected = Err(IntegrationError::InvalidParameter { name: "max_level" });
+        let actual = tanh_sinh(f64::exp, 0.0, 1.0, options);
+
+        assert_eq!(expected, actual);
+    }
+}
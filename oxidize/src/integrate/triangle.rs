@@ -0,0 +1,374 @@
+//! Quadrature over triangles and unstructured triangular meshes.
+
+#![allow(clippy::excessive_precision)]
+
+use super::IntegrationError;
+
+/// The highest polynomial degree a `dunavant_rule` can integrate exactly.
+pub const MAX_DUNAVANT_DEGREE: usize = 6;
+
+/// A symmetric quadrature rule on a triangle.
+///
+/// Points are given in barycentric coordinates and the weights sum to 1,
+/// so a rule is applied to any triangle by mapping the points onto it and
+/// scaling the weighted sum by its area.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TriangleRule {
+    degree: usize,
+    points: &'static [[f64; 3]],
+    weights: &'static [f64],
+}
+
+impl TriangleRule {
+    /// The highest polynomial degree the rule integrates exactly.
+    pub fn degree(&self) -> usize {
+        self.degree
+    }
+
+    /// The barycentric coordinates of the points.
+    pub fn points(&self) -> &[[f64; 3]] {
+        self.points
+    }
+
+    /// The weights, which sum to 1.
+    pub fn weights(&self) -> &[f64] {
+        self.weights
+    }
+
+    /// Applies the rule to `f` over the triangle with the given vertices,
+    /// which may be in either orientation.
+    pub fn integrate<F: Fn(f64, f64) -> f64>(&self, f: F, vertices: [(f64, f64); 3]) -> f64 {
+        let [(x0, y0), (x1, y1), (x2, y2)] = vertices;
+
+        let mut sum = 0.0;
+        for (l, w) in self.points.iter().zip(self.weights) {
+            let x = l[0]*x0 + l[1]*x1 + l[2]*x2;
+            let y = l[0]*y0 + l[1]*y1 + l[2]*y2;
+            sum += w*f(x, y);
+        }
+
+        area(vertices)*sum
+    }
+}
+
+/// Returns the Dunavant rule that integrates polynomials of `degree` exactly
+/// on a triangle.
+///
+/// The rules use 1, 3, 4, 6, 7 and 12 points for degrees 1 to 6. The
+/// degree 3 rule has a negative weight, which can cost accuracy when the
+/// integrand is much larger at the centroid than elsewhere.
+///
+/// Fails if `degree` is 0 or greater than `MAX_DUNAVANT_DEGREE`.
+///
+/// D. A. Dunavant, "High degree efficient symmetrical Gaussian quadrature
+/// rules for the triangle", International Journal for Numerical Methods in
+/// Engineering 21 (1985).
+pub fn dunavant_rule(degree: usize) -> Result<TriangleRule, IntegrationError> {
+    let (points, weights): (&'static [[f64; 3]], &'static [f64]) = match degree {
+        1 => (&DEGREE_1_POINTS, &DEGREE_1_WEIGHTS),
+        2 => (&DEGREE_2_POINTS, &DEGREE_2_WEIGHTS),
+        3 => (&DEGREE_3_POINTS, &DEGREE_3_WEIGHTS),
+        4 => (&DEGREE_4_POINTS, &DEGREE_4_WEIGHTS),
+        5 => (&DEGREE_5_POINTS, &DEGREE_5_WEIGHTS),
+        6 => (&DEGREE_6_POINTS, &DEGREE_6_WEIGHTS),
+        _ => return Err(IntegrationError::InvalidParameter { name: "degree" }),
+    };
+
+    Ok(TriangleRule { degree, points, weights })
+}
+
+/// Computes the integral of `f` over the triangle with the given vertices
+/// using the Dunavant rule of `degree`.
+///
+/// Fails as `dunavant_rule` does, or if a vertex is not finite.
+///
+/// ```
+/// use oxidize::integrate::quad_triangle;
+///
+/// // x*y over the triangle (0, 0), (1, 0), (0, 1)
+/// let area = quad_triangle(|x, y| x*y, [(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)], 2).unwrap();
+/// assert!((area - 1.0/24.0).abs() < 1e-15);
+/// ```
+pub fn quad_triangle<F: Fn(f64, f64) -> f64>(f: F, vertices: [(f64, f64); 3], degree: usize) -> Result<f64, IntegrationError> {
+    let rule = dunavant_rule(degree)?;
+    check_vertices(&vertices)?;
+
+    Ok(rule.integrate(f, vertices))
+}
+
+/// Computes the integral of `f` over a triangular mesh using the Dunavant
+/// rule of `degree` on every triangle.
+///
+/// `triangles` holds the indices of the three vertices of each triangle
+/// into `vertices`. Triangles may be in either orientation, and
+/// overlapping triangles are counted as often as they appear.
+///
+/// Fails as `dunavant_rule` does, if a vertex is not finite or if a
+/// triangle refers to a vertex that does not exist.
+///
+/// ```
+/// use oxidize::integrate::quad_mesh;
+///
+/// // The unit square split along its diagonal
+/// let vertices = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)];
+/// let triangles = [[0, 1, 2], [0, 2, 3]];
+/// let area = quad_mesh(|x, y| x*x + y, &vertices, &triangles, 2).unwrap();
+/// assert!((area - (1.0/3.0 + 0.5)).abs() < 1e-15);
+/// ```
+pub fn quad_mesh<F: Fn(f64, f64) -> f64>(f: F, vertices: &[(f64, f64)], triangles: &[[usize; 3]], degree: usize) -> Result<f64, IntegrationError> {
+    let rule = dunavant_rule(degree)?;
+    check_mesh(vertices, triangles)?;
+
+    Ok(triangles.iter().map(|t| rule.integrate(&f, corners(vertices, t))).sum())
+}
+
+/// Computes the integral of data sampled at the vertices of a triangular
+/// mesh, interpolating linearly across every triangle.
+///
+/// This is the two-dimensional counterpart of `trapezoidal`: each triangle
+/// contributes its area times the mean of the values at its vertices.
+/// `values[i]` is the sample at `vertices[i]`.
+///
+/// Fails as `quad_mesh` does, if `values` and `vertices` differ in length
+/// or if a value is not finite.
+///
+/// ```
+/// use oxidize::integrate::trapezoidal_mesh;
+///
+/// let vertices = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)];
+/// let triangles = [[0, 1, 2], [0, 2, 3]];
+/// // f(x, y) = x + y is linear, so the result is exact
+/// let values = [0.0, 1.0, 2.0, 1.0];
+/// assert_eq!(1.0, trapezoidal_mesh(&vertices, &triangles, &values).unwrap());
+/// ```
+pub fn trapezoidal_mesh(vertices: &[(f64, f64)], triangles: &[[usize; 3]], values: &[f64]) -> Result<f64, IntegrationError> {
+    check_mesh(vertices, triangles)?;
+    if values.len() != vertices.len() {
+        return Err(IntegrationError::ShapeMismatch { expected: vertices.len(), actual: values.len() });
+    }
+    if let Some(index) = values.iter().position(|v| !v.is_finite()) {
+        return Err(IntegrationError::NonFinite { index });
+    }
+
+    let mut sum = 0.0;
+    for t in triangles {
+        let mean = (values[t[0]] + values[t[1]] + values[t[2]])/3.0;
+        sum += area(corners(vertices, t))*mean;
+    }
+
+    Ok(sum)
+}
+
+/// The unsigned area of a triangle.
+fn area(vertices: [(f64, f64); 3]) -> f64 {
+    let [(x0, y0), (x1, y1), (x2, y2)] = vertices;
+
+    0.5*((x1 - x0)*(y2 - y0) - (x2 - x0)*(y1 - y0)).abs()
+}
+
+fn corners(vertices: &[(f64, f64)], triangle: &[usize; 3]) -> [(f64, f64); 3] {
+    [vertices[triangle[0]], vertices[triangle[1]], vertices[triangle[2]]]
+}
+
+fn check_vertices(vertices: &[(f64, f64)]) -> Result<(), IntegrationError> {
+    match vertices.iter().position(|&(x, y)| !(x.is_finite() && y.is_finite())) {
+        Some(index) => Err(IntegrationError::NonFinite { index }),
+        None => Ok(()),
+    }
+}
+
+fn check_mesh(vertices: &[(f64, f64)], triangles: &[[usize; 3]]) -> Result<(), IntegrationError> {
+    check_vertices(vertices)?;
+
+    match triangles.iter().position(|t| t.iter().any(|&v| v >= vertices.len())) {
+        Some(index) => Err(IntegrationError::MissingVertex { triangle: index }),
+        None => Ok(()),
+    }
+}
+
+const DEGREE_1_POINTS: [[f64; 3]; 1] = [[1.0/3.0, 1.0/3.0, 1.0/3.0]];
+const DEGREE_1_WEIGHTS: [f64; 1] = [1.0];
+
+const DEGREE_2_POINTS: [[f64; 3]; 3] = [
+    [2.0/3.0, 1.0/6.0, 1.0/6.0],
+    [1.0/6.0, 2.0/3.0, 1.0/6.0],
+    [1.0/6.0, 1.0/6.0, 2.0/3.0],
+];
+const DEGREE_2_WEIGHTS: [f64; 3] = [1.0/3.0, 1.0/3.0, 1.0/3.0];
+
+const DEGREE_3_POINTS: [[f64; 3]; 4] = [
+    [1.0/3.0, 1.0/3.0, 1.0/3.0],
+    [0.6, 0.2, 0.2],
+    [0.2, 0.6, 0.2],
+    [0.2, 0.2, 0.6],
+];
+const DEGREE_3_WEIGHTS: [f64; 4] = [-27.0/48.0, 25.0/48.0, 25.0/48.0, 25.0/48.0];
+
+const D4_A1: f64 = 0.108103018168070227;
+const D4_B1: f64 = 0.445948490915964886;
+const D4_W1: f64 = 0.223381589678011466;
+const D4_A2: f64 = 0.816847572980458513;
+const D4_B2: f64 = 0.091576213509770743;
+const D4_W2: f64 = 0.109951743655321868;
+
+const DEGREE_4_POINTS: [[f64; 3]; 6] = [
+    [D4_A1, D4_B1, D4_B1],
+    [D4_B1, D4_A1, D4_B1],
+    [D4_B1, D4_B1, D4_A1],
+    [D4_A2, D4_B2, D4_B2],
+    [D4_B2, D4_A2, D4_B2],
+    [D4_B2, D4_B2, D4_A2],
+];
+const DEGREE_4_WEIGHTS: [f64; 6] = [D4_W1, D4_W1, D4_W1, D4_W2, D4_W2, D4_W2];
+
+// Radon's rule, whose coordinates are (9 -+ 2*sqrt(15))/21 and
+// (6 +- sqrt(15))/21 with weights (155 +- sqrt(15))/1200.
+const D5_A1: f64 = 0.059715871789769820;
+const D5_B1: f64 = 0.470142064105115090;
+const D5_W1: f64 = 0.132394152788506181;
+const D5_A2: f64 = 0.797426985353087322;
+const D5_B2: f64 = 0.101286507323456339;
+const D5_W2: f64 = 0.125939180544827153;
+
+const DEGREE_5_POINTS: [[f64; 3]; 7] = [
+    [1.0/3.0, 1.0/3.0, 1.0/3.0],
+    [D5_A1, D5_B1, D5_B1],
+    [D5_B1, D5_A1, D5_B1],
+    [D5_B1, D5_B1, D5_A1],
+    [D5_A2, D5_B2, D5_B2],
+    [D5_B2, D5_A2, D5_B2],
+    [D5_B2, D5_B2, D5_A2],
+];
+const DEGREE_5_WEIGHTS: [f64; 7] = [0.225, D5_W1, D5_W1, D5_W1, D5_W2, D5_W2, D5_W2];
+
+const D6_A1: f64 = 0.501426509658179;
+const D6_B1: f64 = 0.249286745170910;
+const D6_W1: f64 = 0.116786275726379;
+const D6_A2: f64 = 0.873821971016996;
+const D6_B2: f64 = 0.063089014491502;
+const D6_W2: f64 = 0.050844906370207;
+const D6_A3: f64 = 0.053145049844817;
+const D6_B3: f64 = 0.310352451033784;
+const D6_C3: f64 = 0.636502499121399;
+const D6_W3: f64 = 0.082851075618374;
+
+const DEGREE_6_POINTS: [[f64; 3]; 12] = [
+    [D6_A1, D6_B1, D6_B1],
+    [D6_B1, D6_A1, D6_B1],
+    [D6_B1, D6_B1, D6_A1],
+    [D6_A2, D6_B2, D6_B2],
+    [D6_B2, D6_A2, D6_B2],
+    [D6_B2, D6_B2, D6_A2],
+    [D6_A3, D6_B3, D6_C3],
+    [D6_A3, D6_C3, D6_B3],
+    [D6_B3, D6_A3, D6_C3],
+    [D6_B3, D6_C3, D6_A3],
+    [D6_C3, D6_A3, D6_B3],
+    [D6_C3, D6_B3, D6_A3],
+];
+const DEGREE_6_WEIGHTS: [f64; 12] = [
+    D6_W1, D6_W1, D6_W1, D6_W2, D6_W2, D6_W2,
+    D6_W3, D6_W3, D6_W3, D6_W3, D6_W3, D6_W3,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UNIT: [(f64, f64); 3] = [(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)];
+
+    fn factorial(n: u32) -> f64 {
+        (1..n + 1).map(f64::from).product()
+    }
+
+    #[test]
+    fn test_dunavant_weights_sum_to_one() {
+        for degree in 1..MAX_DUNAVANT_DEGREE + 1 {
+            let rule = dunavant_rule(degree).unwrap();
+            let sum: f64 = rule.weights().iter().sum();
+
+            assert_eq!(rule.points().len(), rule.weights().len());
+            assert!((sum - 1.0).abs() < 1e-14, "degree {}", degree);
+        }
+    }
+
+    #[test]
+    fn test_dunavant_exact_for_monomials() {
+        // The integral of x^i*y^j over the unit triangle is i!*j!/(i + j + 2)!
+        for degree in 1..MAX_DUNAVANT_DEGREE + 1 {
+            let rule = dunavant_rule(degree).unwrap();
+            for i in 0..degree as i32 + 1 {
+                for j in 0..degree as i32 + 1 - i {
+                    let expected = factorial(i as u32)*factorial(j as u32)/factorial((i + j + 2) as u32);
+                    let actual = rule.integrate(|x, y| x.powi(i)*y.powi(j), UNIT);
+
+                    assert!((expected - actual).abs() < 1e-13, "degree {}: x^{} y^{}", degree, i, j);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_quad_triangle_orientation() {
+        let f = |x: f64, y: f64| (x + 2.0*y).exp();
+        let forward = quad_triangle(f, [(0.0, 0.0), (2.0, 1.0), (0.5, 3.0)], 6).unwrap();
+        let backward = quad_triangle(f, [(0.5, 3.0), (2.0, 1.0), (0.0, 0.0)], 6).unwrap();
+
+        assert!((forward - backward).abs() < 1e-12*forward.abs());
+    }
+
+    #[test]
+    fn test_quad_mesh_disc() {
+        // A fan of triangles approximating the unit disc; the area converges
+        // to pi as the polygon gains sides.
+        let n = 256;
+        let mut vertices = vec![(0.0, 0.0)];
+        for k in 0..n {
+            let angle = 2.0*std::f64::consts::PI*k as f64/n as f64;
+            vertices.push((angle.cos(), angle.sin()));
+        }
+        let triangles: Vec<[usize; 3]> = (0..n).map(|k| [0, k + 1, (k + 1)%n + 1]).collect();
+
+        let expected = 0.5*n as f64*(2.0*std::f64::consts::PI/n as f64).sin();
+        let actual = quad_mesh(|_, _| 1.0, &vertices, &triangles, 1).unwrap();
+
+        assert!((expected - actual).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_trapezoidal_mesh_matches_degree_1() {
+        let vertices = [(0.0, 0.0), (2.0, 0.0), (2.0, 1.0), (0.0, 1.0), (1.0, 0.5)];
+        let triangles = [[0, 1, 4], [1, 2, 4], [2, 3, 4], [3, 0, 4]];
+        let f = |x: f64, y: f64| 3.0*x - y + 1.0;
+        let values: Vec<f64> = vertices.iter().map(|&(x, y)| f(x, y)).collect();
+
+        let expected = quad_mesh(f, &vertices, &triangles, 1).unwrap();
+        let actual = trapezoidal_mesh(&vertices, &triangles, &values).unwrap();
+
+        assert!((expected - actual).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_quad_mesh_missing_vertex() {
+        let expected = Err(IntegrationError::MissingVertex { triangle: 1 });
+        let actual = quad_mesh(|_, _| 1.0, &UNIT, &[[0, 1, 2], [0, 1, 3]], 1);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_dunavant_invalid_degree() {
+        let expected = Err(IntegrationError::InvalidParameter { name: "degree" });
+
+        assert_eq!(expected, dunavant_rule(0));
+        assert_eq!(expected, dunavant_rule(MAX_DUNAVANT_DEGREE + 1));
+    }
+
+    #[test]
+    fn test_trapezoidal_mesh_value_count() {
+        let expected = Err(IntegrationError::ShapeMismatch { expected: 3, actual: 2 });
+        let actual = trapezoidal_mesh(&UNIT, &[[0, 1, 2]], &[1.0, 2.0]);
+
+        assert_eq!(expected, actual);
+    }
+}
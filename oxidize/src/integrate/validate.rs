@@ -0,0 +1,121 @@
+//! Input checks shared by the `try_` variants of the integration rules.
+//!
+//! `check_spacing` is also public so callers can test the evenly-spaced
+//! assumption of `trapezoidal` and `simpson` before choosing a rule.
+
+use super::samples::Samples;
+use super::{Float, IntegrationError, Ordinate};
+
+/// Relative difference allowed between interval widths before data is
+/// considered unevenly spaced.
+pub const SPACING_TOLERANCE: f64 = 1e-9;
+
+/// The spacing tolerance the `try_` rules use for `T`: `SPACING_TOLERANCE`,
+/// widened to 1000 machine epsilons for types too coarse to meet it.
+pub fn spacing_tolerance<T: Float>() -> T {
+    let tolerance = T::from_f64(SPACING_TOLERANCE);
+    let coarse = T::from_f64(1000.0)*T::epsilon();
+
+    if coarse > tolerance { coarse } else { tolerance }
+}
+
+/// Checks that there are at least `required` points, that every coordinate
+/// is finite and that the abscissae are strictly increasing.
+pub fn check_points<T, Y, S>(data: &S, required: usize) -> Result<(), IntegrationError>
+    where T: Float, Y: Ordinate<T>, S: Samples<T, Y> + ?Sized
+{
+    if data.len() < required {
+        return Err(IntegrationError::TooFewPoints { required, actual: data.len() });
+    }
+
+    for index in 0..data.len() {
+        if !data.x(index).is_finite() || !data.y(index).is_finite() {
+            return Err(IntegrationError::NonFinite { index });
+        }
+
+        if index > 0 && data.x(index) <= data.x(index - 1) {
+            return Err(IntegrationError::Unsorted { index });
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that every interval is as wide as the first one and returns that
+/// width.
+///
+/// Widths may differ from the first one by up to `tolerance` times its
+/// magnitude; `SPACING_TOLERANCE` is what the `try_` rules use for `f64`.
+/// Fails with `TooFewPoints` if there is no interval at all.
+///
+/// ```
+/// use oxidize::integrate::{check_spacing, IntegrationError, SPACING_TOLERANCE};
+///
+/// let even = [(0.0, 1.0), (0.1, 2.0), (0.2, 3.0)];
+/// let uneven = [(0.0, 1.0), (0.1, 2.0), (0.3, 3.0)];
+///
+/// assert_eq!(Ok(0.1), check_spacing(&even, SPACING_TOLERANCE));
+/// assert_eq!(Err(IntegrationError::UnevenSpacing { index: 2 }), check_spacing(&uneven, SPACING_TOLERANCE));
+/// ```
+pub fn check_spacing<T: Float, Y: Ordinate<T>>(data: &[(T,Y)], tolerance: T) -> Result<T, IntegrationError> {
+    check_uniform(data, tolerance)
+}
+
+/// Checks the spacing of any `Samples` as `check_spacing` does.
+pub fn check_uniform<T, Y, S>(data: &S, tolerance: T) -> Result<T, IntegrationError>
+    where T: Float, Y: Ordinate<T>, S: Samples<T, Y> + ?Sized
+{
+    if data.len() < 2 {
+        return Err(IntegrationError::TooFewPoints { required: 2, actual: data.len() });
+    }
+
+    let h = data.x(1) - data.x(0);
+    if data.evenly_spaced() {
+        return Ok(h);
+    }
+
+    for index in 2..data.len() {
+        let width = data.x(index) - data.x(index - 1);
+        if (width - h).abs() > tolerance*h.abs() {
+            return Err(IntegrationError::UnevenSpacing { index });
+        }
+    }
+
+    Ok(h)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_spacing_one_element() {
+        let data = [(0.0, 1.0)];
+
+        let expected = Err(IntegrationError::TooFewPoints { required: 2, actual: 1 });
+        let actual = check_spacing(&data, SPACING_TOLERANCE);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_check_spacing_rounding_error() {
+        // 0.1 is not exactly representable, so the widths differ slightly
+        let data = [(0.0, 0.0), (0.1, 0.0), (0.2, 0.0), (0.30000000000000004, 0.0)];
+
+        let expected = Ok(0.1);
+        let actual = check_spacing(&data, SPACING_TOLERANCE);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_check_spacing_loose_tolerance() {
+        let data = [(0.0, 0.0), (1.0, 0.0), (2.05, 0.0)];
+
+        let expected = Ok(1.0);
+        let actual = check_spacing(&data, 0.1);
+
+        assert_eq!(expected, actual);
+    }
+}
@@ -1,2 +1,35 @@
+//! Numerical methods for Rust.
+//!
+//! Each numerical subsystem lives in its own top-level module:
+//!
+//! * [`integrate`](integrate/index.html) - quadrature rules for sampled data
+//!   and for functions.
+//! * [`montecarlo`](montecarlo/index.html) - Monte Carlo and quasi-Monte Carlo
+//!   integration in many dimensions.
+//! * [`sum`](sum/index.html) - accurate summation, which the sample-based
+//!   rules can use.
+//!
+//! New subsystems are added as siblings of `integrate`, and their most
+//! commonly used items are re-exported from [`prelude`](prelude/index.html)
+//! alongside those of the others, so a single glob import is enough for
+//! most programs:
+//!
+//! ```
+//! use oxidize::prelude::*;
+//!
+//! let data = [(0.0, 1.0), (1.0, 1.0)];
+//! assert_eq!(1.0, trapezoidal(&data));
+//! ```
+//!
+//! With the `parallel` feature, the long sums of the sample-based rules and
+//! the integrand evaluations of the adaptive Gauss–Kronrod rule can be
+//! spread across threads with [rayon](https://docs.rs/rayon); see
+//! `integrate::par_trapezoidal` and `integrate::par_quad_with`.
 
-mod integrate;
\ No newline at end of file
+#[cfg(feature = "parallel")]
+extern crate rayon;
+
+pub mod integrate;
+pub mod montecarlo;
+pub mod prelude;
+pub mod sum;
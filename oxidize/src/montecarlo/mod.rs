@@ -0,0 +1,334 @@
+//! Monte Carlo and quasi-Monte Carlo integration over boxes.
+//!
+//! Where the rules in [`integrate`](../integrate/index.html) need a number
+//! of points that grows exponentially with the dimension, the error of a
+//! Monte Carlo estimate shrinks with the square root of the number of
+//! samples in any dimension. Every integrator takes the box as `lower` and
+//! `upper` corners and an integrand `Fn(&[f64]) -> f64`, and draws its
+//! randomness from an explicitly seeded [`Rng`](struct.Rng.html), so
+//! results are reproducible.
+//!
+//! ```
+//! use oxidize::montecarlo::{self, Rng};
+//!
+//! let mut rng = Rng::new(1);
+//! let f = |p: &[f64]| p[0]*p[1];
+//! let result = montecarlo::plain(f, &[0.0, 0.0], &[1.0, 1.0], 10000, &mut rng).unwrap();
+//! assert!((result.value - 0.25).abs() < 4.0*result.error);
+//! ```
+
+mod rng;
+mod sequence;
+mod vegas;
+
+pub use self::rng::Rng;
+pub use self::sequence::{Halton, LowDiscrepancy, Sobol, MAX_SOBOL_DIMENSIONS};
+pub use self::vegas::{vegas, VegasIteration, VegasOptions, VegasResult};
+
+use integrate::IntegrationError;
+
+/// The outcome of a Monte Carlo integrator.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MonteCarloResult {
+    /// The estimate of the integral.
+    pub value: f64,
+
+    /// The estimated standard error of `value`.
+    pub error: f64,
+
+    /// The number of times the integrand was evaluated.
+    pub samples: usize,
+}
+
+/// Computes the integral of `f` over a box by averaging it at `samples`
+/// uniformly random points.
+///
+/// The error is the sample standard deviation of `f` times the volume,
+/// over the square root of `samples`.
+///
+/// Fails if `lower` and `upper` differ in length or are empty, if any
+/// bound is not finite or if `samples` is less than 2.
+pub fn plain<F: Fn(&[f64]) -> f64>(f: F, lower: &[f64], upper: &[f64], samples: usize, rng: &mut Rng) -> Result<MonteCarloResult, IntegrationError> {
+    let volume = check_box(lower, upper)?;
+    check_samples(samples)?;
+
+    let mut point = vec![0.0; lower.len()];
+    let mut stats = Stats::new();
+    for _ in 0..samples {
+        for (x, (a, b)) in point.iter_mut().zip(lower.iter().zip(upper)) {
+            *x = a + (b - a)*rng.next_f64();
+        }
+        stats.push(f(&point));
+    }
+
+    Ok(MonteCarloResult {
+        value: volume*stats.mean,
+        error: volume*stats.standard_error(),
+        samples,
+    })
+}
+
+/// Computes the integral of `f` over a box by splitting every side into
+/// `strata` equal parts and averaging `f` at `per_stratum` random points
+/// in each of the resulting sub-boxes.
+///
+/// Spreading the points evenly removes the variance between sub-boxes
+/// from the error, which is the root of the sum of the variances of the
+/// sub-box estimates. There are `strata` to the power of the dimension
+/// sub-boxes, so this suits low dimensions.
+///
+/// Fails as `plain` does, if `strata` is 0 or if `per_stratum` is less
+/// than 2.
+pub fn stratified<F: Fn(&[f64]) -> f64>(f: F, lower: &[f64], upper: &[f64], strata: usize, per_stratum: usize, rng: &mut Rng) -> Result<MonteCarloResult, IntegrationError> {
+    let volume = check_box(lower, upper)?;
+    if strata < 1 {
+        return Err(IntegrationError::InvalidParameter { name: "strata" });
+    }
+    check_samples(per_stratum)?;
+
+    let dimensions = lower.len();
+    let cells = (strata as f64).powi(dimensions as i32);
+    let cell_volume = volume/cells;
+
+    let mut value = 0.0;
+    let mut variance = 0.0;
+    let mut samples = 0;
+
+    let mut cell = vec![0; dimensions];
+    let mut point = vec![0.0; dimensions];
+    loop {
+        let mut stats = Stats::new();
+        for _ in 0..per_stratum {
+            for d in 0..dimensions {
+                let width = (upper[d] - lower[d])/strata as f64;
+                point[d] = lower[d] + width*(cell[d] as f64 + rng.next_f64());
+            }
+            stats.push(f(&point));
+        }
+
+        value += cell_volume*stats.mean;
+        variance += (cell_volume*stats.standard_error()).powi(2);
+        samples += per_stratum;
+
+        // Step to the next sub-box like an odometer.
+        let mut d = 0;
+        while d < dimensions {
+            cell[d] += 1;
+            if cell[d] < strata {
+                break;
+            }
+            cell[d] = 0;
+            d += 1;
+        }
+        if d == dimensions {
+            break;
+        }
+    }
+
+    Ok(MonteCarloResult { value, error: variance.sqrt(), samples })
+}
+
+/// Computes the integral of `f` over a box by averaging it at the first
+/// `samples` points of a low-discrepancy `sequence`.
+///
+/// A single quasi-Monte Carlo estimate has no error estimate of its own,
+/// so the points are randomly shifted, modulo 1, `shifts` times and the
+/// error is the standard error over the shifted estimates. The error
+/// typically falls almost as fast as `1/samples`.
+///
+/// Fails as `plain` does, if the sequence has a different number of
+/// dimensions than the box or if `shifts` is less than 2.
+///
+/// ```
+/// use oxidize::montecarlo::{self, Rng, Sobol};
+///
+/// let mut rng = Rng::new(1);
+/// let mut sobol = Sobol::new(3).unwrap();
+/// let f = |p: &[f64]| p[0] + p[1] + p[2];
+/// let result = montecarlo::quasi(f, &[0.0; 3], &[1.0; 3], &mut sobol, 1024, 8, &mut rng).unwrap();
+/// assert!((result.value - 1.5).abs() < 1e-3);
+/// ```
+pub fn quasi<F, S>(f: F, lower: &[f64], upper: &[f64], sequence: &mut S, samples: usize, shifts: usize, rng: &mut Rng) -> Result<MonteCarloResult, IntegrationError>
+    where F: Fn(&[f64]) -> f64,
+          S: LowDiscrepancy
+{
+    let volume = check_box(lower, upper)?;
+    let dimensions = lower.len();
+    if sequence.dimensions() != dimensions {
+        return Err(IntegrationError::DimensionMismatch { expected: dimensions, actual: sequence.dimensions() });
+    }
+    check_samples(samples)?;
+    if shifts < 2 {
+        return Err(IntegrationError::InvalidParameter { name: "shifts" });
+    }
+
+    let mut points = vec![0.0; samples*dimensions];
+    for unit in points.chunks_mut(dimensions) {
+        sequence.next_point(unit);
+    }
+
+    let mut shift = vec![0.0; dimensions];
+    let mut point = vec![0.0; dimensions];
+    let mut estimates = Stats::new();
+    for _ in 0..shifts {
+        for s in &mut shift {
+            *s = rng.next_f64();
+        }
+
+        let mut sum = 0.0;
+        for unit in points.chunks(dimensions) {
+            for d in 0..dimensions {
+                let u = (unit[d] + shift[d]).fract();
+                point[d] = lower[d] + (upper[d] - lower[d])*u;
+            }
+            sum += f(&point);
+        }
+        estimates.push(volume*sum/samples as f64);
+    }
+
+    Ok(MonteCarloResult {
+        value: estimates.mean,
+        error: estimates.standard_error(),
+        samples: samples*shifts,
+    })
+}
+
+/// Checks the corners of a box and returns its volume.
+fn check_box(lower: &[f64], upper: &[f64]) -> Result<f64, IntegrationError> {
+    if lower.len() != upper.len() {
+        return Err(IntegrationError::DimensionMismatch { expected: lower.len(), actual: upper.len() });
+    }
+    if lower.is_empty() {
+        return Err(IntegrationError::InvalidParameter { name: "lower" });
+    }
+    if lower.iter().chain(upper).any(|x| !x.is_finite()) {
+        return Err(IntegrationError::NonFiniteBounds);
+    }
+
+    Ok(lower.iter().zip(upper).map(|(a, b)| b - a).product())
+}
+
+fn check_samples(samples: usize) -> Result<(), IntegrationError> {
+    if samples < 2 {
+        return Err(IntegrationError::TooFewPoints { required: 2, actual: samples });
+    }
+
+    Ok(())
+}
+
+/// A running mean and variance, updated with Welford's method.
+struct Stats {
+    count: usize,
+    mean: f64,
+    sum_squares: f64,
+}
+
+impl Stats {
+    fn new() -> Stats {
+        Stats { count: 0, mean: 0.0, sum_squares: 0.0 }
+    }
+
+    fn push(&mut self, value: f64) {
+        self.count += 1;
+        let delta = value - self.mean;
+        self.mean += delta/self.count as f64;
+        self.sum_squares += delta*(value - self.mean);
+    }
+
+    /// The standard error of the mean.
+    fn standard_error(&self) -> f64 {
+        let variance = self.sum_squares/(self.count - 1) as f64;
+        (variance/self.count as f64).sqrt()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn polynomial(p: &[f64]) -> f64 {
+        // Integrates to 1 over any unit cube at the origin
+        p.iter().map(|x| 2.0*x).product()
+    }
+
+    #[test]
+    fn test_plain_is_reproducible() {
+        let first = plain(polynomial, &[0.0; 4], &[1.0; 4], 1000, &mut Rng::new(3)).unwrap();
+        let second = plain(polynomial, &[0.0; 4], &[1.0; 4], 1000, &mut Rng::new(3)).unwrap();
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_plain_within_error() {
+        let result = plain(polynomial, &[0.0; 4], &[1.0; 4], 100000, &mut Rng::new(11)).unwrap();
+
+        assert_eq!(100000, result.samples);
+        assert!((result.value - 1.0).abs() < 4.0*result.error);
+        assert!(result.error < 0.02);
+    }
+
+    #[test]
+    fn test_plain_scales_by_volume() {
+        let f = |_: &[f64]| 1.0;
+        let result = plain(f, &[0.0, -1.0], &[2.0, 2.0], 10, &mut Rng::new(0)).unwrap();
+
+        assert_eq!(6.0, result.value);
+        assert_eq!(0.0, result.error);
+    }
+
+    #[test]
+    fn test_stratified_reduces_error() {
+        let mut rng = Rng::new(5);
+        let plain = plain(polynomial, &[0.0; 2], &[1.0; 2], 10000, &mut rng).unwrap();
+        let stratified = stratified(polynomial, &[0.0; 2], &[1.0; 2], 25, 16, &mut rng).unwrap();
+
+        assert_eq!(10000, stratified.samples);
+        assert!((stratified.value - 1.0).abs() < 4.0*stratified.error);
+        assert!(stratified.error < 0.2*plain.error);
+    }
+
+    #[test]
+    fn test_quasi_sobol_beats_plain() {
+        let f = |p: &[f64]| p.iter().map(|x| x.exp()).product();
+        let expected = (1f64.exp() - 1.0).powi(3);
+
+        let mut rng = Rng::new(9);
+        let mut sobol = Sobol::new(3).unwrap();
+        let plain = plain(f, &[0.0; 3], &[1.0; 3], 8*4096, &mut rng).unwrap();
+        let quasi = quasi(f, &[0.0; 3], &[1.0; 3], &mut sobol, 4096, 8, &mut rng).unwrap();
+
+        assert_eq!(8*4096, quasi.samples);
+        assert!((quasi.value - expected).abs() < 4.0*quasi.error);
+        assert!(quasi.error < 0.1*plain.error);
+    }
+
+    #[test]
+    fn test_quasi_halton() {
+        let mut halton = Halton::new(3).unwrap();
+        let f = |p: &[f64]| (p[0] + p[1] + p[2]).exp();
+        let expected = (1f64.exp() - 1.0).powi(3);
+        let result = quasi(f, &[0.0; 3], &[1.0; 3], &mut halton, 4000, 10, &mut Rng::new(2)).unwrap();
+
+        assert!((result.value - expected).abs() < 1e-2);
+        assert!((result.value - expected).abs() < 5.0*result.error);
+    }
+
+    #[test]
+    fn test_quasi_dimension_mismatch() {
+        let mut sobol = Sobol::new(2).unwrap();
+
+        let expected = Err(IntegrationError::DimensionMismatch { expected: 3, actual: 2 });
+        let actual = quasi(polynomial, &[0.0; 3], &[1.0; 3], &mut sobol, 64, 4, &mut Rng::new(0));
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_plain_infinite_bound() {
+        let expected = Err(IntegrationError::NonFiniteBounds);
+        let actual = plain(polynomial, &[0.0], &[f64::INFINITY], 64, &mut Rng::new(0));
+
+        assert_eq!(expected, actual);
+    }
+}
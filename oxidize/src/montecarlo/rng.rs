@@ -0,0 +1,90 @@
+//! A small, seedable pseudo-random number generator.
+
+/// The xoshiro256** generator, seeded through SplitMix64.
+///
+/// The same seed always gives the same sequence on every platform, so
+/// Monte Carlo results are reproducible. It is not suitable for
+/// cryptography.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rng {
+    state: [u64; 4],
+}
+
+impl Rng {
+    /// Creates a generator from a seed.
+    pub fn new(seed: u64) -> Rng {
+        let mut seed = seed;
+        let mut state = [0; 4];
+        for word in &mut state {
+            *word = split_mix(&mut seed);
+        }
+
+        Rng { state }
+    }
+
+    /// Returns the next 64 random bits.
+    pub fn next_u64(&mut self) -> u64 {
+        let s = &mut self.state;
+        let result = s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = s[1] << 17;
+
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+
+        result
+    }
+
+    /// Returns a number drawn uniformly from `[0, 1)`.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64*(1.0/(1u64 << 53) as f64)
+    }
+}
+
+fn split_mix(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e3779b97f4a7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_sequence() {
+        let mut first = Rng::new(42);
+        let mut second = Rng::new(42);
+
+        for _ in 0..100 {
+            assert_eq!(first.next_u64(), second.next_u64());
+        }
+    }
+
+    #[test]
+    fn test_different_seeds_differ() {
+        let mut first = Rng::new(1);
+        let mut second = Rng::new(2);
+
+        assert!(first.next_u64() != second.next_u64());
+    }
+
+    #[test]
+    fn test_next_f64_is_uniform() {
+        let mut rng = Rng::new(7);
+        let n = 100000;
+        let mut sum = 0.0;
+        for _ in 0..n {
+            let x = rng.next_f64();
+            assert!((0.0..1.0).contains(&x));
+            sum += x;
+        }
+
+        assert!((sum/n as f64 - 0.5).abs() < 0.01);
+    }
+}
@@ -0,0 +1,251 @@
+//! Low-discrepancy sequences for quasi-Monte Carlo integration.
+
+use integrate::IntegrationError;
+
+/// A deterministic sequence of points spread evenly over the unit cube.
+pub trait LowDiscrepancy {
+    /// The number of coordinates of every point.
+    fn dimensions(&self) -> usize;
+
+    /// Writes the next point of the sequence into `point`, which must have
+    /// `dimensions()` entries.
+    fn next_point(&mut self, point: &mut [f64]);
+}
+
+/// The number of bits of every Sobol coordinate.
+const SOBOL_BITS: usize = 32;
+
+/// Primitive polynomials and initial direction numbers for dimensions 2
+/// onwards, as `(degree, coefficients, initial numbers)`, from Joe and
+/// Kuo's `new-joe-kuo-6.21201`.
+const SOBOL_PARAMETERS: [(usize, u32, &[u32]); 15] = [
+    (1, 0, &[1]),
+    (2, 1, &[1, 3]),
+    (3, 1, &[1, 3, 1]),
+    (3, 2, &[1, 1, 1]),
+    (4, 1, &[1, 1, 3, 3]),
+    (4, 4, &[1, 3, 5, 13]),
+    (5, 2, &[1, 1, 5, 5, 17]),
+    (5, 4, &[1, 1, 5, 5, 5]),
+    (5, 7, &[1, 1, 7, 11, 19]),
+    (5, 11, &[1, 1, 5, 1, 1]),
+    (5, 13, &[1, 1, 1, 3, 11]),
+    (5, 14, &[1, 3, 5, 5, 31]),
+    (6, 1, &[1, 3, 3, 9, 7, 49]),
+    (6, 13, &[1, 1, 1, 15, 21, 21]),
+    (6, 16, &[1, 3, 1, 13, 27, 49]),
+];
+
+/// The most dimensions `Sobol` supports.
+pub const MAX_SOBOL_DIMENSIONS: usize = SOBOL_PARAMETERS.len() + 1;
+
+/// The Sobol sequence, generated in Gray-code order.
+///
+/// The first point is the origin.
+#[derive(Debug, Clone)]
+pub struct Sobol {
+    directions: Vec<[u32; SOBOL_BITS]>,
+    current: Vec<u32>,
+    index: u64,
+}
+
+impl Sobol {
+    /// Creates a Sobol sequence in `dimensions` dimensions.
+    ///
+    /// Fails if `dimensions` is 0 or more than `MAX_SOBOL_DIMENSIONS`.
+    pub fn new(dimensions: usize) -> Result<Sobol, IntegrationError> {
+        if !(1..=MAX_SOBOL_DIMENSIONS).contains(&dimensions) {
+            return Err(IntegrationError::InvalidParameter { name: "dimensions" });
+        }
+
+        let mut directions = Vec::with_capacity(dimensions);
+
+        let mut first = [0; SOBOL_BITS];
+        for (i, v) in first.iter_mut().enumerate() {
+            *v = 1 << (SOBOL_BITS - 1 - i);
+        }
+        directions.push(first);
+
+        for &(degree, coefficients, initial) in &SOBOL_PARAMETERS[..dimensions - 1] {
+            let mut v = [0; SOBOL_BITS];
+            for i in 0..degree {
+                v[i] = initial[i] << (SOBOL_BITS - 1 - i);
+            }
+            for i in degree..SOBOL_BITS {
+                v[i] = v[i - degree] ^ (v[i - degree] >> degree);
+                for k in 1..degree {
+                    if (coefficients >> (degree - 1 - k)) & 1 == 1 {
+                        v[i] ^= v[i - k];
+                    }
+                }
+            }
+            directions.push(v);
+        }
+
+        Ok(Sobol { directions, current: vec![0; dimensions], index: 0 })
+    }
+}
+
+impl LowDiscrepancy for Sobol {
+    fn dimensions(&self) -> usize {
+        self.directions.len()
+    }
+
+    fn next_point(&mut self, point: &mut [f64]) {
+        let scale = 1.0/(1u64 << SOBOL_BITS) as f64;
+        for (x, &c) in point.iter_mut().zip(&self.current) {
+            *x = c as f64*scale;
+        }
+
+        // Moving to the next Gray code flips the bit at the position of
+        // the lowest zero bit of the index.
+        let bit = (!self.index).trailing_zeros() as usize;
+        if bit < SOBOL_BITS {
+            for (c, v) in self.current.iter_mut().zip(&self.directions) {
+                *c ^= v[bit];
+            }
+        }
+        self.index += 1;
+    }
+}
+
+/// The Halton sequence, using the first prime numbers as bases.
+///
+/// The sequence starts at index 1, so the origin is never produced.
+/// Correlations between coordinates grow with the bases, so it works best
+/// in no more than about 10 dimensions.
+#[derive(Debug, Clone)]
+pub struct Halton {
+    bases: Vec<u64>,
+    index: u64,
+}
+
+impl Halton {
+    /// Creates a Halton sequence in `dimensions` dimensions.
+    ///
+    /// Fails if `dimensions` is 0.
+    pub fn new(dimensions: usize) -> Result<Halton, IntegrationError> {
+        if dimensions < 1 {
+            return Err(IntegrationError::InvalidParameter { name: "dimensions" });
+        }
+
+        let mut bases = Vec::with_capacity(dimensions);
+        let mut candidate = 2;
+        while bases.len() < dimensions {
+            if bases.iter().all(|p| candidate % p != 0) {
+                bases.push(candidate);
+            }
+            candidate += 1;
+        }
+
+        Ok(Halton { bases, index: 1 })
+    }
+}
+
+impl LowDiscrepancy for Halton {
+    fn dimensions(&self) -> usize {
+        self.bases.len()
+    }
+
+    fn next_point(&mut self, point: &mut [f64]) {
+        for (x, &base) in point.iter_mut().zip(&self.bases) {
+            *x = radical_inverse(self.index, base);
+        }
+        self.index += 1;
+    }
+}
+
+/// Mirrors the digits of `index` in `base` about the radix point.
+fn radical_inverse(index: u64, base: u64) -> f64 {
+    let inverse_base = 1.0/base as f64;
+    let mut scale = inverse_base;
+    let mut result = 0.0;
+
+    let mut n = index;
+    while n > 0 {
+        result += (n % base) as f64*scale;
+        n /= base;
+        scale *= inverse_base;
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn take<S: LowDiscrepancy>(sequence: &mut S, n: usize) -> Vec<Vec<f64>> {
+        (0..n)
+            .map(|_| {
+                let mut point = vec![0.0; sequence.dimensions()];
+                sequence.next_point(&mut point);
+                point
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_sobol_first_points() {
+        let mut sobol = Sobol::new(2).unwrap();
+
+        let expected = vec![
+            vec![0.0, 0.0],
+            vec![0.5, 0.5],
+            vec![0.75, 0.25],
+            vec![0.25, 0.75],
+            vec![0.375, 0.375],
+            vec![0.875, 0.875],
+        ];
+        let actual = take(&mut sobol, 6);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_sobol_stratifies_every_dimension() {
+        // The first 2^k points of every coordinate fall one in each of the
+        // intervals [i/2^k, (i+1)/2^k)
+        let n = 64;
+        let points = take(&mut Sobol::new(MAX_SOBOL_DIMENSIONS).unwrap(), n);
+
+        for d in 0..MAX_SOBOL_DIMENSIONS {
+            let mut seen = vec![false; n];
+            for point in &points {
+                seen[(point[d]*n as f64) as usize] = true;
+            }
+            assert!(seen.iter().all(|&s| s), "dimension {}", d);
+        }
+    }
+
+    #[test]
+    fn test_sobol_dimensions() {
+        let expected = IntegrationError::InvalidParameter { name: "dimensions" };
+
+        assert_eq!(expected, Sobol::new(0).unwrap_err());
+        assert_eq!(expected, Sobol::new(MAX_SOBOL_DIMENSIONS + 1).unwrap_err());
+    }
+
+    #[test]
+    fn test_halton_first_points() {
+        let mut halton = Halton::new(2).unwrap();
+
+        let expected = [
+            [0.5, 1.0/3.0],
+            [0.25, 2.0/3.0],
+            [0.75, 1.0/9.0],
+        ];
+        let actual = take(&mut halton, 3);
+
+        for (e, a) in expected.iter().zip(&actual) {
+            assert!((e[0] - a[0]).abs() < 1e-15 && (e[1] - a[1]).abs() < 1e-15);
+        }
+    }
+
+    #[test]
+    fn test_halton_bases_are_primes() {
+        let halton = Halton::new(6).unwrap();
+
+        assert_eq!(vec![2, 3, 5, 7, 11, 13], halton.bases);
+    }
+}
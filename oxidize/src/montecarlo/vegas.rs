@@ -0,0 +1,333 @@
+//! The VEGAS adaptive importance-sampling integrator.
+
+use integrate::IntegrationError;
+
+use super::{check_box, Rng, Stats};
+
+/// Options for `vegas`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VegasOptions {
+    /// The number of iterations, each of which refines the grid.
+    pub iterations: usize,
+
+    /// The number of samples drawn in every iteration.
+    pub samples: usize,
+
+    /// The number of bins along every axis of the grid.
+    pub bins: usize,
+
+    /// How aggressively the grid adapts; 0 keeps it uniform and values
+    /// between 1 and 2 are typical.
+    pub alpha: f64,
+
+    /// The number of early iterations left out of the weighted average
+    /// while the grid is still far from converged.
+    pub warmup: usize,
+}
+
+impl Default for VegasOptions {
+    fn default() -> VegasOptions {
+        VegasOptions { iterations: 10, samples: 10000, bins: 50, alpha: 1.5, warmup: 2 }
+    }
+}
+
+/// The estimate of a single VEGAS iteration.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VegasIteration {
+    /// The estimate of the integral from this iteration alone.
+    pub value: f64,
+
+    /// The standard error of `value`.
+    pub error: f64,
+}
+
+/// The outcome of `vegas`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VegasResult {
+    /// The average of the iterations after the warm-up, weighted by their
+    /// inverse variances.
+    pub value: f64,
+
+    /// The standard error of `value`.
+    pub error: f64,
+
+    /// The chi-squared of the averaged iterations per degree of freedom.
+    /// Values much larger than 1 mean the iterations disagree, and the
+    /// error is not to be trusted. It is `NaN` with a single iteration.
+    pub chi_squared_per_dof: f64,
+
+    /// The estimate of every iteration, including the warm-up.
+    pub iterations: Vec<VegasIteration>,
+
+    /// The number of times the integrand was evaluated.
+    pub samples: usize,
+}
+
+/// Computes the integral of `f` over a box using the VEGAS algorithm.
+///
+/// Every axis of the box is split into bins of varying width, and each
+/// sample picks a bin uniformly along every axis, so points crowd where
+/// the bins are narrow. After every iteration the bins are resized so
+/// that each carries an equal share of the integrand's squared magnitude,
+/// concentrating samples where `f` is large. This works best when the
+/// important regions line up with the axes.
+///
+/// Fails as `montecarlo::plain` does, if `samples` is less than 2, if
+/// `bins` is 0, if `alpha` is negative or not finite, or if there are no
+/// iterations after the warm-up.
+///
+/// ```
+/// use oxidize::montecarlo::{vegas, Rng, VegasOptions};
+///
+/// // A narrow Gaussian peak in 4 dimensions, integrating to about 1
+/// let f = |p: &[f64]| {
+///     let r2: f64 = p.iter().map(|x| (x - 0.5)*(x - 0.5)).sum();
+///     (-r2/(2.0*0.01)).exp()/(2.0*std::f64::consts::PI*0.01).powi(2)
+/// };
+/// let result = vegas(f, &[0.0; 4], &[1.0; 4], VegasOptions::default(), &mut Rng::new(1)).unwrap();
+/// assert!((result.value - 1.0).abs() < 5.0*result.error);
+/// ```
+pub fn vegas<F: Fn(&[f64]) -> f64>(f: F, lower: &[f64], upper: &[f64], options: VegasOptions, rng: &mut Rng) -> Result<VegasResult, IntegrationError> {
+    let volume = check_box(lower, upper)?;
+    if options.samples < 2 {
+        return Err(IntegrationError::TooFewPoints { required: 2, actual: options.samples });
+    }
+    if options.bins < 1 {
+        return Err(IntegrationError::InvalidParameter { name: "bins" });
+    }
+    if !(options.alpha >= 0.0 && options.alpha.is_finite()) {
+        return Err(IntegrationError::InvalidParameter { name: "alpha" });
+    }
+    if options.iterations <= options.warmup {
+        return Err(IntegrationError::InvalidParameter { name: "iterations" });
+    }
+
+    let dimensions = lower.len();
+    let bins = options.bins;
+    let mut grid: Vec<Grid> = (0..dimensions).map(|_| Grid::uniform(bins)).collect();
+
+    let mut point = vec![0.0; dimensions];
+    let mut chosen = vec![0; dimensions];
+    let mut iterations = Vec::with_capacity(options.iterations);
+
+    for _ in 0..options.iterations {
+        let mut stats = Stats::new();
+        let mut squares: Vec<Vec<f64>> = vec![vec![0.0; bins]; dimensions];
+
+        for _ in 0..options.samples {
+            let mut jacobian = volume;
+            for d in 0..dimensions {
+                let (u, bin, width) = grid[d].sample(rng.next_f64());
+                point[d] = lower[d] + (upper[d] - lower[d])*u;
+                chosen[d] = bin;
+                jacobian *= bins as f64*width;
+            }
+
+            let weighted = f(&point)*jacobian;
+            stats.push(weighted);
+            for d in 0..dimensions {
+                squares[d][chosen[d]] += weighted*weighted;
+            }
+        }
+
+        iterations.push(VegasIteration { value: stats.mean, error: stats.standard_error() });
+
+        for (axis, squares) in grid.iter_mut().zip(&squares) {
+            axis.refine(squares, options.alpha);
+        }
+    }
+
+    let (value, error, chi_squared_per_dof) = combine(&iterations[options.warmup..]);
+
+    Ok(VegasResult {
+        value,
+        error,
+        chi_squared_per_dof,
+        iterations,
+        samples: options.iterations*options.samples,
+    })
+}
+
+/// Averages iterations weighted by their inverse variances, returning the
+/// average, its standard error and the chi-squared per degree of freedom.
+fn combine(iterations: &[VegasIteration]) -> (f64, f64, f64) {
+    // An iteration with no spread at all would get an infinite weight, so
+    // every variance is kept above the rounding error of its value.
+    let variance = |it: &VegasIteration| {
+        let floor = f64::EPSILON*it.value.abs();
+        (it.error*it.error).max(floor*floor).max(f64::MIN_POSITIVE)
+    };
+
+    let mut weights = 0.0;
+    let mut sum = 0.0;
+    for it in iterations {
+        let weight = 1.0/variance(it);
+        weights += weight;
+        sum += weight*it.value;
+    }
+    let value = sum/weights;
+
+    let mut chi_squared = 0.0;
+    for it in iterations {
+        chi_squared += (it.value - value)*(it.value - value)/variance(it);
+    }
+    let dof = iterations.len() as f64 - 1.0;
+
+    (value, (1.0/weights).sqrt(), chi_squared/dof)
+}
+
+/// The bin edges along one axis, over `[0, 1]`.
+struct Grid {
+    edges: Vec<f64>,
+}
+
+impl Grid {
+    fn uniform(bins: usize) -> Grid {
+        Grid { edges: (0..bins + 1).map(|i| i as f64/bins as f64).collect() }
+    }
+
+    fn bins(&self) -> usize {
+        self.edges.len() - 1
+    }
+
+    /// Maps a uniform number in `[0, 1)` to a point on the axis, returning
+    /// the point, the bin it fell in and that bin's width.
+    fn sample(&self, y: f64) -> (f64, usize, f64) {
+        let scaled = y*self.bins() as f64;
+        let bin = (scaled as usize).min(self.bins() - 1);
+        let width = self.edges[bin + 1] - self.edges[bin];
+
+        (self.edges[bin] + (scaled - bin as f64)*width, bin, width)
+    }
+
+    /// Resizes the bins so each holds an equal share of the smoothed,
+    /// damped `squares` collected in them.
+    fn refine(&mut self, squares: &[f64], alpha: f64) {
+        let n = self.bins();
+        if n < 2 {
+            return;
+        }
+
+        // Smooth each bin with its neighbours.
+        let mut smoothed = vec![0.0; n];
+        smoothed[0] = 0.5*(squares[0] + squares[1]);
+        smoothed[n - 1] = 0.5*(squares[n - 2] + squares[n - 1]);
+        for i in 1..n - 1 {
+            smoothed[i] = (squares[i - 1] + squares[i] + squares[i + 1])/3.0;
+        }
+
+        let total: f64 = smoothed.iter().sum();
+        if !(total > 0.0 && total.is_finite()) {
+            return;
+        }
+
+        // Damp the shares, following Lepage, so the grid does not swing
+        // wildly between iterations.
+        let importance: Vec<f64> = smoothed
+            .iter()
+            .map(|&s| {
+                let share = s/total;
+                if share <= 0.0 {
+                    0.0
+                } else if share >= 1.0 {
+                    1.0
+                } else {
+                    ((share - 1.0)/share.ln()).powf(alpha)
+                }
+            })
+            .collect();
+
+        let per_bin = importance.iter().sum::<f64>()/n as f64;
+        if per_bin <= 0.0 {
+            return;
+        }
+
+        let mut edges = Vec::with_capacity(n + 1);
+        edges.push(0.0);
+
+        let mut old = 0;
+        let mut accumulated = 0.0;
+        for i in 1..n {
+            let target = per_bin*i as f64;
+            while old < n - 1 && accumulated + importance[old] < target {
+                accumulated += importance[old];
+                old += 1;
+            }
+
+            let fraction = if importance[old] > 0.0 {
+                ((target - accumulated)/importance[old]).min(1.0)
+            } else {
+                0.0
+            };
+            let width = self.edges[old + 1] - self.edges[old];
+            edges.push(self.edges[old] + fraction*width);
+        }
+
+        edges.push(1.0);
+        self.edges = edges;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use montecarlo::plain;
+
+    fn peak(p: &[f64]) -> f64 {
+        // A Gaussian of width 0.05 centred in the unit box, integrating to
+        // 1 in any dimension up to negligible tails
+        let sigma2 = 0.05*0.05;
+        let r2: f64 = p.iter().map(|x| (x - 0.5)*(x - 0.5)).sum();
+        (-r2/(2.0*sigma2)).exp()/(2.0*std::f64::consts::PI*sigma2).powf(p.len() as f64/2.0)
+    }
+
+    #[test]
+    fn test_vegas_peak_beats_plain() {
+        let options = VegasOptions::default();
+        let mut rng = Rng::new(4);
+        let vegas = vegas(peak, &[0.0; 4], &[1.0; 4], options, &mut rng).unwrap();
+        let plain = plain(peak, &[0.0; 4], &[1.0; 4], vegas.samples, &mut rng).unwrap();
+
+        assert_eq!(10, vegas.iterations.len());
+        assert!((vegas.value - 1.0).abs() < 5.0*vegas.error);
+        assert!(vegas.error < 0.1*plain.error);
+        assert!(vegas.chi_squared_per_dof < 5.0);
+    }
+
+    #[test]
+    fn test_vegas_is_reproducible() {
+        let options = VegasOptions { samples: 500, iterations: 4, ..VegasOptions::default() };
+        let first = vegas(peak, &[0.0; 3], &[1.0; 3], options, &mut Rng::new(8)).unwrap();
+        let second = vegas(peak, &[0.0; 3], &[1.0; 3], options, &mut Rng::new(8)).unwrap();
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_vegas_constant() {
+        let options = VegasOptions { samples: 100, iterations: 3, warmup: 0, ..VegasOptions::default() };
+        let result = vegas(|_: &[f64]| 2.0, &[0.0, 0.0], &[1.0, 3.0], options, &mut Rng::new(0)).unwrap();
+
+        assert!((result.value - 6.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_grid_refine_concentrates_bins() {
+        let mut grid = Grid::uniform(4);
+        grid.refine(&[0.0, 1.0, 1.0, 0.0], 1.0);
+
+        // The middle bins carry all the weight, so they narrow
+        assert!(grid.edges[2] == 0.5);
+        assert!(grid.edges[1] > 0.25 && grid.edges[3] < 0.75);
+    }
+
+    #[test]
+    fn test_vegas_no_iterations_after_warmup() {
+        let options = VegasOptions { iterations: 2, warmup: 2, ..VegasOptions::default() };
+
+        let expected = Err(IntegrationError::InvalidParameter { name: "iterations" });
+        let actual = vegas(peak, &[0.0], &[1.0], options, &mut Rng::new(0));
+
+        assert_eq!(expected, actual);
+    }
+}
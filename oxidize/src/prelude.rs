@@ -0,0 +1,44 @@
+//! Convenience re-exports of the most commonly used items of every
+//! subsystem.
+//!
+//! ```
+//! use oxidize::prelude::*;
+//!
+//! let data = [(0.0, 0.0), (0.5, 0.25), (1.0, 1.0)];
+//! assert_eq!(1.0/3.0, simpson(&data));
+//! assert_eq!(Ok(0.5), try_trapezoidal(&[(0.0, 0.0), (1.0, 1.0)]));
+//!
+//! let result = adaptive_simpson(|x: f64| x*x, 0.0, 1.0, Tolerance::default()).unwrap();
+//! assert!((result.value - 1.0/3.0).abs() < 1e-10);
+//!
+//! let result = quad_with(|x: f64| x*x, 0.0, 1.0, QuadOptions::default()).unwrap();
+//! assert_eq!(Termination::Converged, result.termination);
+//!
+//! let mut rng = Rng::new(1);
+//! let estimate: MonteCarloResult = oxidize::montecarlo::plain(|p: &[f64]| p[0], &[0.0], &[1.0], 10000, &mut rng).unwrap();
+//! assert!((estimate.value - 0.5).abs() < 4.0*estimate.error);
+//!
+//! let area = vegas(|p: &[f64]| p[0], &[0.0], &[1.0], VegasOptions::default(), &mut Rng::new(1)).unwrap();
+//! assert!((area.value - 0.5).abs() < 1e-2);
+//!
+//! let oscillation = adaptive_simpson(|x: f64| Complex::new(x.cos(), x.sin()), 0.0, 1.0, Tolerance::default()).unwrap();
+//! assert!((oscillation.value.im - (1.0 - 1f64.cos())).abs() < 1e-8);
+//!
+//! assert_eq!(1.0/3.0, simpson_with(&data, Summation::Neumaier));
+//! assert_eq!(0.5, trapezoidal_with(&[(0.0, 0.0), (1.0, 1.0)], Summation::Kahan));
+//!
+//! let mut integrator = StreamingIntegrator::new(StreamingRule::Simpson);
+//! integrator.extend(data.iter().cloned()).unwrap();
+//! assert_eq!(1.0/3.0, integrator.value());
+//! ```
+
+pub use integrate::{quad, simpson, trapezoidal};
+pub use integrate::{try_simpson, try_trapezoidal, IntegrationError};
+pub use integrate::{adaptive_simpson, QuadratureResult, Tolerance};
+pub use integrate::{quad_with, QuadOptions, Termination};
+pub use montecarlo::{MonteCarloResult, Rng};
+pub use montecarlo::{vegas, VegasOptions};
+pub use integrate::Complex;
+pub use integrate::{simpson_with, trapezoidal_with};
+pub use sum::Summation;
+pub use integrate::{StreamingIntegrator, StreamingRule};
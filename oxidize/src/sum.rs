@@ -0,0 +1,378 @@
+//! Accurate summation of long sequences of floating-point numbers.
+//!
+//! Adding `n` numbers one after another can lose up to `n` roundings'
+//! worth of accuracy, which over millions of samples costs several digits.
+//! The methods of [`Summation`](enum.Summation.html) trade a few extra
+//! operations per term for an error that grows much more slowly, or not at
+//! all. They work on any [`Ordinate`](../integrate/trait.Ordinate.html),
+//! treating complex numbers component by component, and the sample-based
+//! rules in `integrate` can be told which one to use through their `_with`
+//! variants. [`RunningSum`](struct.RunningSum.html) adds up terms that
+//! arrive one at a time with the methods that need only a running
+//! compensation, the [`OnlineSummation`](enum.OnlineSummation.html)s.
+//!
+//! ```
+//! use oxidize::sum::Summation;
+//!
+//! let values = [1.0, 1e100, 1.0, -1e100];
+//! assert_eq!(0.0, Summation::Naive.sum_slice(&values));
+//! assert_eq!(2.0, Summation::Neumaier.sum_slice(&values));
+//! ```
+
+use std::marker::PhantomData;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use integrate::{Float, Ordinate};
+
+/// The number of terms below which pairwise summation adds naively.
+pub const PAIRWISE_BLOCK: usize = 32;
+
+/// The number of terms each thread adds up at a time in `par_sum`.
+#[cfg(feature = "parallel")]
+pub const PARALLEL_CHUNK: usize = 1 << 16;
+
+/// A way of adding up a sequence of numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Summation {
+    /// One addition after another. The error can grow with the number of
+    /// terms.
+    #[default]
+    Naive,
+
+    /// Kahan's compensated summation, which carries the rounding error of
+    /// every addition into the next term. Fails to help when a term is
+    /// much larger than the running sum.
+    Kahan,
+
+    /// Neumaier's improvement of Kahan's method, which recovers the exact
+    /// rounding error of every addition whichever operand is larger, and
+    /// adds the errors up separately. The result is as accurate as if it
+    /// had been added naively in twice the precision.
+    Neumaier,
+
+    /// Recursive halving of the sequence, so the error grows with the
+    /// logarithm of the number of terms at little extra cost.
+    Pairwise,
+
+    /// Shewchuk's exact accumulation, which keeps the running sum as a
+    /// short list of non-overlapping partial sums. The partial sums are
+    /// exact, and adding them up at the end, from the largest down, gives
+    /// a result within one unit in the last place of the exact sum, though
+    /// not always the nearest to it, as long as nothing overflows.
+    Exact,
+}
+
+/// A way of adding up a sequence one term at a time in constant memory:
+/// the methods of `Summation` that need only a running compensation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OnlineSummation {
+    /// As `Summation::Naive`.
+    #[default]
+    Naive,
+
+    /// As `Summation::Kahan`.
+    Kahan,
+
+    /// As `Summation::Neumaier`.
+    Neumaier,
+}
+
+impl From<OnlineSummation> for Summation {
+    fn from(summation: OnlineSummation) -> Summation {
+        match summation {
+            OnlineSummation::Naive => Summation::Naive,
+            OnlineSummation::Kahan => Summation::Kahan,
+            OnlineSummation::Neumaier => Summation::Neumaier,
+        }
+    }
+}
+
+impl Summation {
+    /// Returns `init + term(0) + ... + term(n - 1)`, added with this method.
+    pub fn sum<T, Y, F>(self, init: Y, n: usize, term: F) -> Y
+        where T: Float, Y: Ordinate<T>, F: Fn(usize) -> Y
+    {
+        let online = match self {
+            Summation::Naive => OnlineSummation::Naive,
+            Summation::Kahan => OnlineSummation::Kahan,
+            Summation::Neumaier => OnlineSummation::Neumaier,
+            Summation::Pairwise => return init + pairwise(0, n, &term),
+            Summation::Exact => {
+                let mut partials = vec![init];
+                for k in 0..n {
+                    grow(&mut partials, term(k));
+                }
+                return partials.iter().rev().fold(Y::zero(), |sum, &p| sum + p);
+            }
+        };
+
+        let mut result = RunningSum::start(online, init);
+        for k in 0..n {
+            result.add(term(k));
+        }
+        result.value()
+    }
+
+
+    /// Returns the sum of `values`, added with this method.
+    pub fn sum_slice<T: Float, Y: Ordinate<T>>(self, values: &[Y]) -> Y {
+        self.sum(Y::zero(), values.len(), |k| values[k])
+    }
+
+    /// Returns `init + term(0) + ... + term(n - 1)`, added with this method
+    /// on several threads.
+    ///
+    /// The terms are split into runs of `PARALLEL_CHUNK`, each run is added
+    /// up on its own, and the sums of the runs are then added in order.
+    /// Where the runs are split does not depend on the number of threads,
+    /// so the result is the same from one run to the next and on any
+    /// machine. It matches `sum` exactly when there is only one run, and
+    /// otherwise may differ from it in the last bits. With
+    /// `Summation::Exact` only the sum of each run is exact.
+    #[cfg(feature = "parallel")]
+    pub fn par_sum<T, Y, F>(self, init: Y, n: usize, term: F) -> Y
+        where T: Float, Y: Ordinate<T> + Send, F: Fn(usize) -> Y + Sync
+    {
+        if n <= PARALLEL_CHUNK {
+            return self.sum(init, n, term);
+        }
+
+        let chunks = n.div_ceil(PARALLEL_CHUNK);
+        let partials: Vec<Y> = (0..chunks).into_par_iter().map(|chunk| {
+            let start = chunk*PARALLEL_CHUNK;
+            let len = PARALLEL_CHUNK.min(n - start);
+            self.sum(Y::zero(), len, |k| term(start + k))
+        }).collect();
+
+        self.sum(init, partials.len(), |k| partials[k])
+    }
+}
+
+/// A sum whose terms are added one at a time, as they become known.
+///
+/// Only the running sum and, for the compensated methods, the running
+/// compensation are kept. The terms are added exactly as `Summation::sum`
+/// adds them, so the value is always what it would return for the terms
+/// so far.
+///
+/// ```
+/// use oxidize::sum::{OnlineSummation, RunningSum};
+///
+/// let mut sum = RunningSum::new(OnlineSummation::Neumaier);
+/// for &x in &[1.0, 1e100, 1.0, -1e100] {
+///     sum.add(x);
+/// }
+/// assert_eq!(2.0, sum.value());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RunningSum<T = f64, Y = f64> {
+    summation: OnlineSummation,
+    sum: Y,
+
+    /// The rounding error carried into the next term by Kahan's method, or
+    /// the sum of the rounding errors so far by Neumaier's.
+    compensation: Y,
+
+    scalar: PhantomData<T>,
+}
+
+impl<T: Float, Y: Ordinate<T>> Default for RunningSum<T, Y> {
+    /// A sum of no terms, added naively.
+    fn default() -> RunningSum<T, Y> {
+        RunningSum::new(OnlineSummation::Naive)
+    }
+}
+
+impl<T: Float, Y: Ordinate<T>> RunningSum<T, Y> {
+    /// Creates a sum of no terms, added with `summation`.
+    pub fn new(summation: OnlineSummation) -> RunningSum<T, Y> {
+        RunningSum::start(summation, Y::zero())
+    }
+
+    fn start(summation: OnlineSummation, init: Y) -> RunningSum<T, Y> {
+        RunningSum { summation, sum: init, compensation: Y::zero(), scalar: PhantomData }
+    }
+
+    /// The method the terms are added with.
+    pub fn summation(&self) -> OnlineSummation {
+        self.summation
+    }
+
+    /// Adds the next term.
+    pub fn add(&mut self, term: Y) {
+        match self.summation {
+            OnlineSummation::Kahan => {
+                let y = term - self.compensation;
+                let t = self.sum + y;
+                self.compensation = (t - self.sum) - y;
+                self.sum = t;
+            }
+            OnlineSummation::Neumaier => {
+                let (sum, error) = two_sum(self.sum, term);
+                self.sum = sum;
+                self.compensation += error;
+            }
+            OnlineSummation::Naive => self.sum += term,
+        }
+    }
+
+    /// The sum of the terms added so far.
+    pub fn value(&self) -> Y {
+        match self.summation {
+            OnlineSummation::Neumaier => self.sum + self.compensation,
+            OnlineSummation::Naive | OnlineSummation::Kahan => self.sum,
+        }
+    }
+}
+
+/// Returns `a + b` rounded, and the exact error of that rounding.
+///
+/// This is Knuth's branch-free form, which needs no comparison of
+/// magnitudes and so works component by component.
+fn two_sum<T: Float, Y: Ordinate<T>>(a: Y, b: Y) -> (Y, Y) {
+    let sum = a + b;
+    let b_virtual = sum - a;
+    let a_virtual = sum - b_virtual;
+
+    (sum, (a - a_virtual) + (b - b_virtual))
+}
+
+fn pairwise<T, Y, F>(start: usize, n: usize, term: &F) -> Y
+    where T: Float, Y: Ordinate<T>, F: Fn(usize) -> Y
+{
+    if n <= PAIRWISE_BLOCK {
+        let mut result = Y::zero();
+        for k in start..start + n {
+            result += term(k);
+        }
+        return result;
+    }
+
+    let half = n/2;
+    pairwise(start, half, term) + pairwise(start + half, n - half, term)
+}
+
+/// Adds `x` to the non-overlapping partial sums, keeping them
+/// non-overlapping and in increasing order of magnitude.
+fn grow<T: Float, Y: Ordinate<T>>(partials: &mut Vec<Y>, x: Y) {
+    let mut x = x;
+    let mut kept = 0;
+
+    for i in 0..partials.len() {
+        let (sum, error) = two_sum(x, partials[i]);
+        if error != Y::zero() {
+            partials[kept] = error;
+            kept += 1;
+        }
+        x = sum;
+    }
+
+    partials.truncate(kept);
+    partials.push(x);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use integrate::Complex;
+
+    const ALL: [Summation; 5] = [
+        Summation::Naive,
+        Summation::Kahan,
+        Summation::Neumaier,
+        Summation::Pairwise,
+        Summation::Exact,
+    ];
+
+    #[test]
+    fn test_sum_empty() {
+        for &summation in &ALL {
+            assert_eq!(0.0, summation.sum_slice::<f64, f64>(&[]));
+            assert_eq!(3.0, summation.sum(3.0, 0, |_| 1.0));
+        }
+    }
+
+    #[test]
+    fn test_running_sum_matches_sum() {
+        let terms: Vec<f64> = (1..1000).map(|k| 1.0/k as f64 - 1e-3*(k % 7) as f64).collect();
+
+        for &summation in &[OnlineSummation::Naive, OnlineSummation::Kahan, OnlineSummation::Neumaier] {
+            let mut running = RunningSum::new(summation);
+            for &term in &terms {
+                running.add(term);
+            }
+
+            assert_eq!(Summation::from(summation).sum_slice(&terms), running.value(), "{:?}", summation);
+        }
+    }
+
+    #[test]
+    fn test_sum_large_term_defeats_kahan() {
+        let values = [1.0, 1e100, 1.0, -1e100];
+
+        assert_eq!(0.0, Summation::Kahan.sum_slice(&values));
+        assert_eq!(2.0, Summation::Neumaier.sum_slice(&values));
+        assert_eq!(2.0, Summation::Exact.sum_slice(&values));
+    }
+
+    #[test]
+    fn test_sum_repeated_tenth() {
+        // 2^20 copies of 0.1 sum exactly to 2^20 times 0.1 in floating point
+        let n = 1 << 20;
+        let expected = n as f64*0.1;
+
+        let naive = Summation::Naive.sum(0.0, n, |_| 0.1);
+        assert!((naive - expected).abs() > 1e-8);
+
+        for &summation in &ALL[1..] {
+            let actual = summation.sum(0.0, n, |_| 0.1);
+            assert!((actual - expected).abs() <= 1e-10*expected, "{:?}", summation);
+        }
+        assert_eq!(expected, Summation::Exact.sum(0.0, n, |_| 0.1));
+        assert_eq!(expected, Summation::Neumaier.sum(0.0, n, |_| 0.1));
+    }
+
+    #[test]
+    fn test_sum_exact_cancellation() {
+        // Every term but the tiny ones cancels
+        let values = [1e16, 1.0, 1e-16, -1e16, 3.0, -1e-16, 1e300, -1e300];
+
+        let expected = 4.0;
+        let actual = Summation::Exact.sum_slice(&values);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_sum_complex() {
+        let values = [
+            Complex::new(1.0, 1e100),
+            Complex::new(1e100, 1.0),
+            Complex::new(1.0, -1e100),
+            Complex::new(-1e100, 1.0),
+        ];
+
+        let expected = Complex::new(2.0, 2.0);
+        let actual = Summation::Neumaier.sum_slice(&values);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_par_sum_chunks() {
+        let n = 3*PARALLEL_CHUNK + 17;
+        let term = |k: usize| 1.0/(k as f64 + 1.0);
+
+        for &summation in &ALL {
+            let serial = summation.sum(0.5, n, term);
+            let parallel = summation.par_sum(0.5, n, term);
+            assert!((serial - parallel).abs() < 1e-12, "{:?}", summation);
+
+            let small = PARALLEL_CHUNK - 1;
+            assert_eq!(summation.sum(0.5, small, term), summation.par_sum(0.5, small, term));
+        }
+    }
+}